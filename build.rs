@@ -5,6 +5,7 @@ use autocfg::AutoCfg;
 fn main() -> Result<(), Box<dyn Error>> {
     println!("cargo:rerun-if-changed=build.rs");
     println!("cargo:rerun-if-changed=path/to/Cargo.lock");
+    println!("cargo:rustc-check-cfg=cfg(let_else)");
     let ac = AutoCfg::new()?;
     // Test that we meet a minimum of 1.65 for let else
     if ac.probe_rustc_version(1, 65) {
@@ -0,0 +1,27 @@
+//! Run an action with the result of the calculation
+//!
+//! Prints the tag that would be created for the next version and the
+//! commit it would point at. Replace the body of the callback to create
+//! the tag, update a file or send a notification.
+//!
+//! ```sh
+//! cargo run --example on_result
+//! ```
+
+fn main() -> Result<(), nextsv::Error> {
+    let calculator = nextsv::VersionCalculator::new("v")?.walk_commits()?;
+
+    let answer = calculator.on_result(|answer, repo| {
+        let head = repo
+            .head()
+            .ok()
+            .and_then(|head| head.target())
+            .map(|oid| oid.to_string())
+            .unwrap_or_default();
+        println!("tag {} would point at {}", answer.version_number, head);
+    })?;
+
+    println!("level: {}", answer.bump_level);
+
+    Ok(())
+}
@@ -0,0 +1,109 @@
+//! The consistency of the version tags
+//!
+//! `VersionCalculator::audit_tags_in` checks every version tag in the
+//! repository and reports the issues found in a single `TagAudit`.
+//!
+
+use std::fmt;
+
+use crate::Semantic;
+
+/// An issue found in the version tags
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TagIssue {
+    /// the tag starts with the version prefix but failed to parse
+    Unparsed(String, String),
+    /// the version is tagged on different commits
+    Ambiguous(String, git2::Oid, git2::Oid),
+    /// the release is not the next major, minor or patch of the
+    /// release before it (e.g. 1.0.0 followed by 1.2.0)
+    Gap(Semantic, Semantic),
+    /// the release is tagged on an ancestor of the commit tagged with
+    /// the lower release before it
+    OutOfOrder(Semantic, Semantic),
+    /// the pre-release was never released and a later release exists
+    NotPromoted(Semantic),
+}
+
+impl fmt::Display for TagIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TagIssue::Unparsed(name, e) => write!(f, "tag {} unparsed: {}", name, e),
+            TagIssue::Ambiguous(version, first, second) => write!(
+                f,
+                "version {} tagged on different commits: {} and {}",
+                version, first, second
+            ),
+            TagIssue::Gap(previous, next) => {
+                write!(f, "gap from {} to {}", previous, next)
+            }
+            TagIssue::OutOfOrder(previous, next) => write!(
+                f,
+                "{} is tagged on an ancestor of the commit tagged {}",
+                next, previous
+            ),
+            TagIssue::NotPromoted(version) => {
+                write!(f, "pre-release {} was never released", version)
+            }
+        }
+    }
+}
+
+/// The report of the version tags checked
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TagAudit {
+    /// the number of versions tagged
+    pub versions: usize,
+    /// the issues found
+    pub issues: Vec<TagIssue>,
+}
+
+impl TagAudit {
+    /// Report if no issues were found
+    ///
+    pub fn is_consistent(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+impl fmt::Display for TagAudit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} version(s) tagged", self.versions)?;
+        if self.is_consistent() {
+            return write!(f, ", no issues found");
+        }
+        write!(f, ", {} issue(s) found", self.issues.len())?;
+        for issue in &self.issues {
+            write!(f, "\n- {}", issue)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_lists_the_issues() {
+        let version = |tag| Semantic::parse(tag, "v").unwrap();
+        let mut audit = TagAudit {
+            versions: 3,
+            issues: vec![],
+        };
+        assert_eq!("3 version(s) tagged, no issues found", audit.to_string());
+
+        audit.issues = vec![
+            TagIssue::Gap(version("v1.0.0"), version("v1.2.0")),
+            TagIssue::NotPromoted(version("v1.0.1-rc.1")),
+        ];
+        let expected = "\
+3 version(s) tagged, 2 issue(s) found
+- gap from v1.0.0 to v1.2.0
+- pre-release v1.0.1-rc.1 was never released";
+        assert!(!audit.is_consistent());
+        assert_eq!(expected, audit.to_string());
+    }
+}
@@ -7,13 +7,34 @@
 //!
 //!
 
-use crate::{ConventionalCommits, Error, Level, Semantic, TypeHierarchy};
-use git2::Repository;
-use std::{collections::HashSet, ffi::OsString, fmt};
+use crate::{
+    notes::{self, Directives},
+    Changelog, ConventionalCommits, DepsBump, Error, Level, ParsedCommit, PlanMetrics, PlanOptions,
+    PreRelease, PreReleaseStyle, ReleasePlan, RevertBump, Semantic, State, TagAudit, TagGlob,
+    TagIssue, TagTemplate, TypeHierarchy, UnknownType,
+};
+use clap::ValueEnum;
+use git2::{Repository, RepositoryState};
+use std::{
+    collections::{HashMap, HashSet},
+    ffi::OsString,
+    fmt,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+const CONFIG_TAG_PREFIX: &str = "nextsv.tagPrefix";
+// number of commits walked between checks of the timeout
+const TIMEOUT_CHECK_INTERVAL: usize = 100;
+const DEFAULT_SKIP_MARKER: &str = "[skip release]";
 
 /// Struct the store the result of the calculation (the "answer" :) )
 ///
-#[derive(Debug)]
+/// The version number calculated is never lower than the current version.
+/// When there is nothing to bump the current version is returned with a
+/// bump level of `Level::None`.
+///
+#[derive(Debug, Clone)]
 pub struct Answer {
     /// the semantic level bump calcuated based on conventional commits
     pub bump_level: Level,
@@ -22,6 +43,11 @@ pub struct Answer {
     pub version_number: Semantic,
     /// the change level calculated during the review of conventional commits
     pub change_level: Option<TypeHierarchy>,
+    /// the name of the version tag used as the baseline for the calculation
+    pub baseline_tag: String,
+    /// the id of the commit the walk started from (HEAD unless verifying)
+    /// or empty if the commits were not walked
+    pub head_oid: String,
 }
 
 impl Answer {
@@ -36,6 +62,8 @@ impl Answer {
             bump_level,
             version_number,
             change_level,
+            baseline_tag: String::new(),
+            head_oid: String::new(),
         }
     }
     /// Unwrap the change_level
@@ -47,30 +75,419 @@ impl Answer {
     pub fn change_level(&self) -> TypeHierarchy {
         self.change_level.clone().unwrap_or(TypeHierarchy::Other)
     }
+
+    /// Report if a breaking change was found
+    ///
+    /// The change level is checked rather than the bump level, which is
+    /// lower for a breaking change before 1.0.0.
+    ///
+    pub fn is_breaking_change(&self) -> bool {
+        self.change_level == Some(TypeHierarchy::Breaking)
+    }
 }
 
-/// The latest semantic version tag (vx.y.z)
+/// The candidate next versions at each level
+///
+/// Each version is bumped from the current version whatever the commits
+/// found, so a person can pick the level to release.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Previews {
+    /// the version for a patch bump
+    pub patch: Semantic,
+    /// the version for a minor bump
+    pub minor: Semantic,
+    /// the version for a major bump
+    pub major: Semantic,
+    /// the rules before 1.0.0 apply, so the commits never bump the major
+    /// number and the major bump is the first production release
+    pub initial_development: bool,
+}
+
+impl Previews {
+    /// Report the previews with their labels in level order
+    ///
+    /// The major preview is labelled "first production" when the rules
+    /// before 1.0.0 apply.
+    ///
+    pub fn labelled(&self) -> [(&'static str, &Semantic); 3] {
+        let major = if self.initial_development {
+            "first production"
+        } else {
+            "major"
+        };
+        [
+            ("patch", &self.patch),
+            ("minor", &self.minor),
+            (major, &self.major),
+        ]
+    }
+}
+
+impl fmt::Display for Previews {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let previews: Vec<String> = self
+            .labelled()
+            .iter()
+            .map(|(label, version)| format!("{}: {}", label, version))
+            .collect();
+        write!(f, "{}", previews.join(", "))
+    }
+}
+
+/// A tag matching the version prefix that could not be parsed
+///
+/// Holds the name of the tag and the error reported by `Semantic::parse`.
+///
+pub type SkippedTag = (String, Error);
+
+/// A version tagged on more than one commit
+///
+/// Holds the version and the two commits tagged with it.
+///
+type AmbiguousTag = (String, git2::Oid, git2::Oid);
+
+/// Scan the tags for semantic versions
 ///
-pub fn latest(version_prefix: &str) -> Result<Semantic, Error> {
-    let repo = Repository::open(".")?;
-    log::debug!("repo opened to find latest");
+/// Returns the versions found, with the commit each tag points at, and
+/// the tags matching the prefix that failed to parse. Tags where the
+/// prefix is not followed by a number (e.g. `vnotes` for the prefix "v")
+/// are not version tags and are left out.
+///
+/// If the prefix is matched ignoring ASCII case the version keeps the
+/// prefix as written in the tag so that it reports the tag name. If a
+/// glob is given it is used instead of the prefix.
+///
+#[allow(clippy::type_complexity)]
+fn scan_tags(
+    repo: &Repository,
+    version_prefix: &str,
+    style: PreReleaseStyle,
+    ignore_case: bool,
+    glob: Option<&TagGlob>,
+) -> Result<(Vec<(Semantic, Option<git2::Oid>)>, Vec<SkippedTag>), Error> {
     let mut versions = vec![];
-    repo.tag_foreach(|_id, name| {
+    let mut skipped = vec![];
+    repo.tag_foreach(|id, name| {
         if let Ok(name) = String::from_utf8(name.to_owned()) {
             if let Some(name) = name.strip_prefix("refs/tags/") {
-                if name.starts_with(version_prefix) {
-                    if let Ok(semantic_version) = Semantic::parse(name, version_prefix) {
-                        log::trace!("found qualifying tag {}", &semantic_version);
-                        versions.push(semantic_version);
+                let matched = match glob {
+                    Some(glob) => glob
+                        .version_part(name)
+                        .map(|version| (version, glob.prefix())),
+                    None => matched_prefix(name, version_prefix, ignore_case)
+                        .map(|prefix| (name, prefix)),
+                };
+                let matched = matched.filter(|(version, prefix)| {
+                    let is_number =
+                        version[prefix.len()..].starts_with(|c: char| c.is_ascii_digit());
+                    if !is_number {
+                        log::debug!("tag {} is not a version tag", name);
+                    }
+                    is_number
+                });
+                if let Some((version, prefix)) = matched {
+                    match Semantic::parse_with_style(version, prefix, style) {
+                        Ok(semantic_version) => {
+                            log::trace!("found qualifying tag {}", &semantic_version);
+                            let semantic_version = semantic_version.with_original_tag(name);
+                            let commit = repo
+                                .find_object(id, None)
+                                .and_then(|object| object.peel_to_commit())
+                                .map(|commit| commit.id())
+                                .ok();
+                            versions.push((semantic_version, commit));
+                        }
+                        Err(e) => {
+                            log::warn!("tag {} skipped: {}", name, e);
+                            skipped.push((name.to_string(), e));
+                        }
                     }
                 }
             }
         }
         true
     })?;
+    Ok((versions, skipped))
+}
+
+/// The prefix of the tag name if it matches the version prefix
+///
+/// The prefix is returned as written in the tag name.
+///
+fn matched_prefix<'a>(name: &'a str, version_prefix: &str, ignore_case: bool) -> Option<&'a str> {
+    name.get(..version_prefix.len()).filter(|prefix| {
+        *prefix == version_prefix || (ignore_case && prefix.eq_ignore_ascii_case(version_prefix))
+    })
+}
+
+/// How a tag was treated when looking for version tags
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TagStatus {
+    /// the tag does not start with the version prefix
+    NoPrefix,
+    /// the tag parsed as the version
+    Parsed(Semantic),
+    /// the tag starts with the version prefix but failed to parse
+    Unparsed(String),
+}
+
+/// The kind of version tags used as the baseline
+///
+/// Annotated tags are tag objects with a message (e.g. `git tag -a`) and
+/// lightweight tags are refs to a commit.
+///
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReleaseTags {
+    /// annotated and lightweight tags (the default)
+    Any,
+    /// annotated tags only
+    Annotated,
+    /// lightweight tags only
+    Lightweight,
+}
+
+impl Default for ReleaseTags {
+    fn default() -> Self {
+        ReleaseTags::Any
+    }
+}
+
+impl ReleaseTags {
+    /// Report if the tag named is of the kind
+    ///
+    fn matches(&self, repo: &Repository, tag_name: &str) -> bool {
+        if *self == ReleaseTags::Any {
+            return true;
+        }
+        let annotated = repo
+            .find_reference(&format!("refs/tags/{}", tag_name))
+            .ok()
+            .and_then(|reference| reference.target())
+            .map_or(false, |oid| repo.find_tag(oid).is_ok());
+        annotated == (*self == ReleaseTags::Annotated)
+    }
+}
+
+/// A tag in the repository reported by `VersionCalculator::list_tags_in`
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagListing {
+    /// the name of the tag
+    pub name: String,
+    /// how the tag was treated
+    pub status: TagStatus,
+    /// the tag is the latest version tag
+    pub latest: bool,
+}
+
+impl fmt::Display for TagListing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let marker = if self.latest { '*' } else { ' ' };
+        match &self.status {
+            TagStatus::NoPrefix => write!(f, "{} {} (no prefix)", marker, self.name),
+            TagStatus::Parsed(version) if self.latest => {
+                write!(f, "{} {} parsed as {} (latest)", marker, self.name, version)
+            }
+            TagStatus::Parsed(version) => {
+                write!(f, "{} {} parsed as {}", marker, self.name, version)
+            }
+            TagStatus::Unparsed(e) => write!(f, "{} {} unparsed: {}", marker, self.name, e),
+        }
+    }
+}
+
+/// Find the versions tagged on different commits
+///
+/// Different tags can parse to the same version (e.g. `V1.2.3` and
+/// `v1.2.3` when ignoring the case of the prefix) leaving the release
+/// history ambiguous.
+///
+fn ambiguous_tags(versions: &[(Semantic, Option<git2::Oid>)]) -> Vec<AmbiguousTag> {
+    let mut found: Vec<(&Semantic, git2::Oid)> = vec![];
+    let mut ambiguous = vec![];
+    for (version, commit) in versions {
+        let commit = match commit {
+            Some(commit) => *commit,
+            None => continue,
+        };
+        match found.iter().find(|(v, _)| *v == version) {
+            Some((_, first)) if *first != commit => {
+                ambiguous.push((version.to_string(), *first, commit));
+            }
+            Some(_) => {}
+            None => found.push((version, commit)),
+        }
+    }
+    ambiguous
+}
+
+/// Check the versions tagged for gaps, releases tagged out of order and
+/// pre-releases that were never released
+///
+/// The releases are checked in order against the release before them.
+/// A pre-release is only reported once a later release exists, so the
+/// pre-releases of the next release are not issues.
+///
+fn audit_versions(repo: &Repository, versions: &[(Semantic, Option<git2::Oid>)]) -> Vec<TagIssue> {
+    let mut issues = vec![];
+    let mut releases: Vec<&(Semantic, Option<git2::Oid>)> = versions
+        .iter()
+        .filter(|(version, _)| !version.is_pre_release())
+        .collect();
+    releases.sort_by(|(a, _), (b, _)| a.cmp(b));
+    releases.dedup_by(|(a, _), (b, _)| a == b);
+    for pair in releases.windows(2) {
+        let ((previous, previous_commit), (next, next_commit)) = (pair[0], pair[1]);
+        let successors = [
+            previous.clone().increment_patch().clone(),
+            previous.clone().increment_minor().clone(),
+            previous.clone().increment_major().clone(),
+        ];
+        if !successors.contains(next) {
+            issues.push(TagIssue::Gap(previous.clone(), next.clone()));
+        }
+        if let (Some(previous_commit), Some(next_commit)) = (previous_commit, next_commit) {
+            if repo
+                .graph_descendant_of(*previous_commit, *next_commit)
+                .unwrap_or(false)
+            {
+                issues.push(TagIssue::OutOfOrder(previous.clone(), next.clone()));
+            }
+        }
+    }
+    if let Some((latest, _)) = releases.last() {
+        let mut pre_releases: Vec<&Semantic> = versions
+            .iter()
+            .map(|(version, _)| version)
+            .filter(|version| version.is_pre_release() && *version < latest)
+            .filter(|version| {
+                let core = version.truncate_to(Level::Patch);
+                !releases.iter().any(|(release, _)| *release == core)
+            })
+            .collect();
+        pre_releases.sort();
+        pre_releases.dedup();
+        for version in pre_releases {
+            issues.push(TagIssue::NotPromoted(version.clone()));
+        }
+    }
+    issues
+}
 
-    versions.sort();
+/// Check the next version is greater than every existing version
+///
+/// Returns the highest existing version that is not lower than the
+/// next version, if any.
+///
+fn regression<'a>(next: &Semantic, existing: &'a [Semantic]) -> Option<&'a Semantic> {
+    existing.iter().filter(|version| *version >= next).max()
+}
+
+/// The next pre-release for the core version and label
+///
+/// Continues from the highest existing pre-release of the core version
+/// for the label or starts the series at 1.
+///
+fn pre_release_version(
+    core: Semantic,
+    label: &str,
+    existing: &[Semantic],
+) -> Result<Semantic, Error> {
+    let highest = existing
+        .iter()
+        .filter(|version| version.truncate_to(Level::Patch) == core)
+        .filter(|version| {
+            version
+                .pre_release()
+                .and_then(|pre_release| pre_release.identifiers().first())
+                .map_or(false, |stage| stage.to_string() == label)
+        })
+        .max();
+    match highest {
+        Some(highest) => {
+            log::debug!("continuing pre-release series from {}", highest);
+            let mut version = highest.clone();
+            version.increment_pre_release()?;
+            Ok(version)
+        }
+        None => {
+            let mut version = core;
+            version.start_pre_release(label)?;
+            log::debug!("starting pre-release series {}", &version);
+            Ok(version)
+        }
+    }
+}
+
+/// Sort the versions found removing duplicates
+///
+/// Versions are equal whatever the prefix so a version tagged more than
+/// once is listed once.
+///
+fn sorted_versions(versions: Vec<(Semantic, Option<git2::Oid>)>) -> Vec<Semantic> {
+    let mut versions: Vec<Semantic> = versions.into_iter().map(|(version, _)| version).collect();
+    Semantic::sort(&mut versions);
+    versions.dedup();
     log::debug!("versions sorted");
+    versions
+}
+
+/// Convert the days since 1970-01-01 to a date (e.g. 2023-01-21)
+///
+/// Uses the civil-from-days algorithm of Howard Hinnant.
+///
+fn civil_date(days: i64) -> String {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// The latest semantic version tag (vx.y.z)
+///
+/// A warning is logged for each version tagged on different commits.
+///
+pub fn latest(
+    repo: &Repository,
+    version_prefix: &str,
+    style: PreReleaseStyle,
+    ignore_case: bool,
+    glob: Option<&TagGlob>,
+) -> Result<Semantic, Error> {
+    let (versions, _) = scan_tags(repo, version_prefix, style, ignore_case, glob)?;
+    latest_of(versions)
+}
+
+/// Report if the commit is HEAD or an ancestor of HEAD
+///
+fn on_branch(repo: &Repository, head: git2::Oid, commit: Option<git2::Oid>) -> bool {
+    commit.map_or(false, |commit| {
+        commit == head || repo.graph_descendant_of(head, commit).unwrap_or(false)
+    })
+}
+
+/// The latest of the versions scanned
+///
+/// A warning is logged for each version tagged on different commits.
+///
+fn latest_of(versions: Vec<(Semantic, Option<git2::Oid>)>) -> Result<Semantic, Error> {
+    for (version, first, second) in ambiguous_tags(&versions) {
+        log::warn!(
+            "version {} is tagged on different commits: {} and {}",
+            version,
+            first,
+            second
+        );
+    }
+    let versions = sorted_versions(versions);
 
     match versions.last().cloned() {
         Some(v) => {
@@ -81,12 +498,55 @@ pub fn latest(version_prefix: &str) -> Result<Semantic, Error> {
     }
 }
 
+/// Refuse to walk the commits while a rebase or bisect is in progress
+///
+/// HEAD is a commit part way through the operation so the version would
+/// be misleading. Other operations in progress (e.g. a merge) leave HEAD
+/// on the branch and are only reported as a warning.
+///
+fn check_state(state: RepositoryState) -> Result<(), Error> {
+    let busy = match state {
+        RepositoryState::Clean => return Ok(()),
+        RepositoryState::Rebase
+        | RepositoryState::RebaseInteractive
+        | RepositoryState::RebaseMerge
+        | RepositoryState::ApplyMailboxOrRebase => "rebase",
+        RepositoryState::ApplyMailbox => "git am",
+        RepositoryState::Bisect => "bisect",
+        other => {
+            log::warn!("repository state is {:?}, the version may change", other);
+            return Ok(());
+        }
+    };
+    Err(Error::RepositoryBusy(busy.to_string()))
+}
+
+/// The commit the tag points at
+///
+/// Returns None if the tag does not resolve to a commit.
+///
+fn tag_commit(repo: &Repository, tag_name: &str) -> Option<git2::Oid> {
+    let commit = repo
+        .revparse_single(&format!("refs/tags/{}", tag_name))
+        .and_then(|object| object.peel_to_commit());
+    match commit {
+        Ok(commit) => {
+            log::trace!("tag {} points at {}", tag_name, commit.id());
+            Some(commit.id())
+        }
+        Err(e) => {
+            log::warn!("tag {} not resolved to a commit: {}", tag_name, e);
+            None
+        }
+    }
+}
+
 /// The options for choosing the level of a forced change
 ///
 /// The enum is used by the force method to define the level
 /// at which the forced change is made.
 ///
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub enum ForceLevel {
     /// force change to the major component of semver
     Major,
@@ -106,6 +566,85 @@ impl fmt::Display for ForceLevel {
     }
 }
 
+impl TryFrom<ForceOptions> for ForceLevel {
+    type Error = Error;
+
+    /// Convert the force option to the level of the change
+    ///
+    /// ## Error
+    ///
+    /// Report error for `ForceOptions::First` as the first production
+    /// release is not a change level (see `promote_first`).
+    ///
+    fn try_from(option: ForceOptions) -> Result<Self, Self::Error> {
+        match option {
+            ForceOptions::Major => Ok(ForceLevel::Major),
+            ForceOptions::Minor => Ok(ForceLevel::Minor),
+            ForceOptions::Patch => Ok(ForceLevel::Patch),
+            ForceOptions::First => Err(Error::NotForceLevel(option.to_string())),
+        }
+    }
+}
+
+impl TryFrom<Level> for ForceLevel {
+    type Error = Error;
+
+    /// Convert the level to the level of a forced change
+    ///
+    /// ## Error
+    ///
+    /// Report error for levels other than major, minor and patch.
+    ///
+    fn try_from(level: Level) -> Result<Self, Self::Error> {
+        match level {
+            Level::Major => Ok(ForceLevel::Major),
+            Level::Minor => Ok(ForceLevel::Minor),
+            Level::Patch => Ok(ForceLevel::Patch),
+            _ => Err(Error::NotForceLevel(level.to_string())),
+        }
+    }
+}
+
+/// The options for forcing a change offered to the command line
+///
+/// The change levels convert to a `ForceLevel`. `First` promotes the
+/// version to the first production release (see `promote_first`).
+///
+#[derive(Debug, PartialEq, Eq, Clone, Copy, ValueEnum)]
+pub enum ForceOptions {
+    /// force change to the major component of semver
+    Major,
+    /// force change to the minor component of semver
+    Minor,
+    /// force change to the patch component of semver
+    Patch,
+    /// force the first production release (1.0.0)
+    First,
+}
+
+impl fmt::Display for ForceOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ForceOptions::Major => write!(f, "major"),
+            ForceOptions::Minor => write!(f, "minor"),
+            ForceOptions::Patch => write!(f, "patch"),
+            ForceOptions::First => write!(f, "first"),
+        }
+    }
+}
+
+/// How the current version was found
+///
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Baseline {
+    /// the latest version tag
+    Latest,
+    /// given and the tag for the version was found
+    Tagged,
+    /// given without a tag, the walk stops at the baseline commit if known
+    Untagged,
+}
+
 /// VersionCalculator
 ///
 /// Builds up data about the current version to calculate the next version
@@ -113,9 +652,44 @@ impl fmt::Display for ForceLevel {
 ///
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct VersionCalculator {
+    repo_path: PathBuf,
     current_version: Semantic,
     conventional: Option<ConventionalCommits>,
     files: Option<HashSet<OsString>>,
+    distance: u32,
+    head_oid: Option<git2::Oid>,
+    notes_ref: Option<String>,
+    breaking_scope: Option<String>,
+    baseline_oid: Option<git2::Oid>,
+    base_ref: Option<String>,
+    merge_base: Option<(String, String)>,
+    baseline_commit: Option<String>,
+    ignore_types: Vec<String>,
+    skip_markers: Vec<String>,
+    collapse_types: Vec<String>,
+    allowed_types: Option<Vec<String>>,
+    breaking_footer_always: bool,
+    deps_bump: DepsBump,
+    revert_bump: RevertBump,
+    type_levels: HashMap<String, TypeHierarchy>,
+    unknown_type: UnknownType,
+    expand_squash_body: bool,
+    prefix_ignore_case: bool,
+    tag_glob: Option<TagGlob>,
+    tag_template: Option<TagTemplate>,
+    baseline: Baseline,
+    walk_from: Option<git2::Oid>,
+    zero_feat_as_patch: bool,
+    semver_strict: bool,
+    min_feats_for_minor: u32,
+    reset_pre_release: bool,
+    timeout: Option<Duration>,
+    pre_release_style: PreReleaseStyle,
+    branch_tags_only: bool,
+    release_tags: ReleaseTags,
+    author_stats: bool,
+    authors: HashMap<String, u32>,
+    since_release: Option<(Semantic, Level)>,
 }
 
 impl VersionCalculator {
@@ -126,112 +700,1400 @@ impl VersionCalculator {
     ///  - version_prefix - identifies version tags
     ///
     pub fn new(version_prefix: &str) -> Result<VersionCalculator, Error> {
-        let current_version = latest(version_prefix)?;
-        Ok(VersionCalculator {
+        VersionCalculator::new_in(".", version_prefix)
+    }
+
+    /// Create a new VersionCalculator struct for the repository at a path
+    ///
+    /// The repository is discovered from the path as git does, so the
+    /// path may be a subdirectory of the repository or a linked worktree.
+    ///
+    /// ## Parameters
+    ///
+    ///  - path - the path to the repository or a directory in it
+    ///  - version_prefix - identifies version tags
+    ///
+    pub fn new_in<P: AsRef<Path>>(
+        path: P,
+        version_prefix: &str,
+    ) -> Result<VersionCalculator, Error> {
+        let repo_path = path.as_ref().to_path_buf();
+        let repo = Repository::discover(&repo_path)?;
+        log::debug!("repo opened to find latest");
+        let current_version = latest(&repo, version_prefix, PreReleaseStyle::SemVer, false, None)?;
+        let baseline_oid = tag_commit(&repo, &current_version.tag_name());
+        Ok(VersionCalculator::with_baseline(
+            repo_path,
             current_version,
-            conventional: None,
-            files: None,
-        })
+            Baseline::Latest,
+            baseline_oid,
+        ))
     }
 
-    /// Report the current_version
+    /// Create a new VersionCalculator struct falling back to a default
+    /// version when there are no version tags
     ///
-    pub fn name(&self) -> Semantic {
-        self.current_version.clone()
+    /// With no version tag the calculator starts from the default version
+    /// as `with_current_version` does, so all of the commits are walked.
+    ///
+    /// ## Parameters
+    ///
+    ///  - version_prefix - identifies version tags
+    ///  - default - the version to start from if no version tag is found
+    ///
+    pub fn new_or_default(
+        version_prefix: &str,
+        default: Semantic,
+    ) -> Result<VersionCalculator, Error> {
+        VersionCalculator::new_or_default_in(".", version_prefix, default)
     }
 
-    /// Report top level
+    /// Create a new VersionCalculator struct for the repository at a path
+    /// falling back to a default version when there are no version tags
     ///
-    pub fn top_level(&self) -> Option<TypeHierarchy> {
-        if self.conventional.is_none() {
-            None
-        } else {
-            self.conventional.clone().unwrap().top_type()
+    /// See `new_or_default`.
+    ///
+    /// ## Parameters
+    ///
+    ///  - path - the path to the repository or a directory in it
+    ///  - version_prefix - identifies version tags
+    ///  - default - the version to start from if no version tag is found
+    ///
+    pub fn new_or_default_in<P: AsRef<Path>>(
+        path: P,
+        version_prefix: &str,
+        default: Semantic,
+    ) -> Result<VersionCalculator, Error> {
+        match VersionCalculator::new_in(path.as_ref(), version_prefix) {
+            Err(Error::NoVersionTag) => {
+                log::debug!("no version tag so starting from {}", &default);
+                VersionCalculator::with_current_version_in(path, default)
+            }
+            result => result,
         }
     }
 
-    /// The count of commits of a type in the conventional commits field
+    /// Create a new VersionCalculator struct matching the version prefix
+    /// ignoring ASCII case
+    ///
+    /// Tags such as `V1.2.3` are found with the prefix "v". The prefix
+    /// of the tag found is kept for the baseline and the next version.
     ///
     /// ## Parameters
     ///
-    /// - commit_type - identifies the type of commit e.g. "feat"
+    ///  - version_prefix - identifies version tags
     ///
-    /// ## Error handling
+    pub fn new_ignore_case(version_prefix: &str) -> Result<VersionCalculator, Error> {
+        VersionCalculator::new_ignore_case_in(".", version_prefix)
+    }
+
+    /// Create a new VersionCalculator struct for the repository at a path
+    /// matching the version prefix ignoring ASCII case
     ///
-    /// If there are no conventional commits it returns 0.
-    /// If conventional is None returns 0.
+    /// ## Parameters
     ///
-    pub fn count_commits_by_type(&self, commit_type: &str) -> u32 {
-        match self.conventional.clone() {
-            Some(conventional) => conventional
-                .counts()
-                .get(commit_type)
-                .unwrap_or(&0_u32)
-                .to_owned(),
-            None => 0_u32,
-        }
+    ///  - path - the path to the repository
+    ///  - version_prefix - identifies version tags
+    ///
+    pub fn new_ignore_case_in<P: AsRef<Path>>(
+        path: P,
+        version_prefix: &str,
+    ) -> Result<VersionCalculator, Error> {
+        let repo_path = path.as_ref().to_path_buf();
+        let repo = Repository::discover(&repo_path)?;
+        log::debug!("repo opened to find latest ignoring case of the prefix");
+        let current_version = latest(&repo, version_prefix, PreReleaseStyle::SemVer, true, None)?;
+        let baseline_oid = tag_commit(&repo, &current_version.tag_name());
+        let mut calculator = VersionCalculator::with_baseline(
+            repo_path,
+            current_version,
+            Baseline::Latest,
+            baseline_oid,
+        );
+        calculator.prefix_ignore_case = true;
+        Ok(calculator)
     }
 
-    /// Report the status of the breaking flag in the conventional commits
+    /// Create a new VersionCalculator struct finding the version tags
+    /// with a glob instead of a prefix
     ///
-    /// ## Error Handling
+    /// See `TagGlob` for the tags matched.
     ///
-    /// If the conventional is None returns false
+    /// ## Parameters
     ///
-    pub fn breaking(&self) -> bool {
-        match self.conventional.clone() {
-            Some(conventional) => conventional.breaking(),
-            None => false,
-        }
+    ///  - glob - identifies version tags
+    ///
+    pub fn new_tag_glob(glob: &TagGlob) -> Result<VersionCalculator, Error> {
+        VersionCalculator::new_tag_glob_in(".", glob)
     }
 
-    /// Force update next_version to return a specific result
+    /// Create a new VersionCalculator struct for the repository at a path
+    /// finding the version tags with a glob
     ///
-    /// Options are defined in `ForceLevel`
+    /// ## Parameters
     ///
-    pub fn force(&mut self, level: ForceLevel) -> Self {
-        let mut conventional_commits = ConventionalCommits::new();
-        log::debug!("forcing a change to {}", level);
-        match level {
-            ForceLevel::Major => {
-                conventional_commits.set_breaking(true);
-            }
-            ForceLevel::Minor => {
-                conventional_commits.increment_counts(git_conventional::Type::FEAT);
-            }
-            ForceLevel::Patch => {
-                conventional_commits.increment_counts(git_conventional::Type::FIX);
-            }
-        }
-
-        self.conventional = Some(conventional_commits);
-        self.clone()
+    ///  - path - the path to the repository
+    ///  - glob - identifies version tags
+    ///
+    pub fn new_tag_glob_in<P: AsRef<Path>>(
+        path: P,
+        glob: &TagGlob,
+    ) -> Result<VersionCalculator, Error> {
+        let repo_path = path.as_ref().to_path_buf();
+        let repo = Repository::discover(&repo_path)?;
+        log::debug!("repo opened to find latest matching {}", glob);
+        let current_version = latest(
+            &repo,
+            glob.prefix(),
+            PreReleaseStyle::SemVer,
+            false,
+            Some(glob),
+        )?;
+        let baseline_oid = tag_commit(&repo, &glob.tag_name(&current_version));
+        let mut calculator = VersionCalculator::with_baseline(
+            repo_path,
+            current_version,
+            Baseline::Latest,
+            baseline_oid,
+        );
+        calculator.tag_glob = Some(glob.clone());
+        Ok(calculator)
     }
 
-    /// Get the conventional commits created since the tag was created
+    /// Create a new VersionCalculator struct finding the version tags
+    /// named by the template
     ///
-    /// Uses `git2` to open the repository and walk back to the
-    /// latest version tag collecting the conventional commits.
+    /// The tags are found with the glob of the template and the tag
+    /// created for the next version is named by the template.
     ///
-    /// ## Error Handling
+    ///  - template - names the version tags
     ///
-    /// Errors from 'git2' are returned.
+    pub fn new_tag_template(template: &TagTemplate) -> Result<VersionCalculator, Error> {
+        VersionCalculator::new_tag_template_in(".", template)
+    }
+
+    /// Create a new VersionCalculator struct for the repository at a path
+    /// finding the version tags named by the template
     ///
-    pub fn walk_commits(mut self) -> Result<Self, Error> {
-        let repo = git2::Repository::open(".")?;
-        log::debug!("repo opened to find conventional commits");
-        let mut revwalk = repo.revwalk()?;
-        revwalk.set_sorting(git2::Sort::NONE)?;
-        revwalk.push_head()?;
-        log::debug!("starting the walk from the HEAD");
-        let glob = format!("refs/tags/{}", &self.current_version);
-        revwalk.hide_ref(&glob)?;
-        log::debug!("hide commits from {}", &self.current_version);
+    /// ## Parameters
+    ///
+    ///  - path - the path to the repository
+    ///  - template - names the version tags
+    ///
+    pub fn new_tag_template_in<P: AsRef<Path>>(
+        path: P,
+        template: &TagTemplate,
+    ) -> Result<VersionCalculator, Error> {
+        let mut calculator = VersionCalculator::new_tag_glob_in(path, template.glob())?;
+        calculator.tag_template = Some(template.clone());
+        Ok(calculator)
+    }
 
-        macro_rules! filter_try {
-            ($e:expr) => {
-                match $e {
-                    Ok(t) => t,
+    /// Create a new VersionCalculator struct with the current version given
+    ///
+    /// The version tags are not scanned for the latest version. If a tag
+    /// for the version is found the walk stops at the tag, otherwise all
+    /// of the commits are walked.
+    ///
+    /// ## Parameters
+    ///
+    ///  - current_version - the version to calculate the next version from
+    ///
+    pub fn with_current_version(current_version: Semantic) -> Result<VersionCalculator, Error> {
+        VersionCalculator::with_current_version_in(".", current_version)
+    }
+
+    /// Create a new VersionCalculator struct for the repository at a path
+    /// with the current version given
+    ///
+    /// See `with_current_version`.
+    ///
+    /// ## Parameters
+    ///
+    ///  - path - the path to the repository
+    ///  - current_version - the version to calculate the next version from
+    ///
+    pub fn with_current_version_in<P: AsRef<Path>>(
+        path: P,
+        current_version: Semantic,
+    ) -> Result<VersionCalculator, Error> {
+        let repo_path = path.as_ref().to_path_buf();
+        let repo = Repository::discover(&repo_path)?;
+        let calculator = match tag_commit(&repo, &current_version.tag_name()) {
+            Some(oid) => VersionCalculator::with_baseline(
+                repo_path,
+                current_version,
+                Baseline::Tagged,
+                Some(oid),
+            ),
+            None => {
+                log::debug!("no tag for {} so walking all commits", &current_version);
+                VersionCalculator::with_baseline(
+                    repo_path,
+                    current_version,
+                    Baseline::Untagged,
+                    None,
+                )
+            }
+        };
+        Ok(calculator)
+    }
+
+    fn with_baseline(
+        repo_path: PathBuf,
+        current_version: Semantic,
+        baseline: Baseline,
+        baseline_oid: Option<git2::Oid>,
+    ) -> VersionCalculator {
+        VersionCalculator {
+            repo_path,
+            current_version,
+            conventional: None,
+            files: None,
+            distance: 0,
+            head_oid: None,
+            notes_ref: None,
+            breaking_scope: None,
+            baseline_oid,
+            base_ref: None,
+            merge_base: None,
+            baseline_commit: None,
+            ignore_types: vec![],
+            skip_markers: vec![DEFAULT_SKIP_MARKER.to_string()],
+            collapse_types: vec![],
+            allowed_types: None,
+            breaking_footer_always: false,
+            deps_bump: DepsBump::Patch,
+            revert_bump: RevertBump::Fix,
+            type_levels: HashMap::new(),
+            unknown_type: UnknownType::Patch,
+            expand_squash_body: false,
+            prefix_ignore_case: false,
+            tag_glob: None,
+            tag_template: None,
+            baseline,
+            walk_from: None,
+            zero_feat_as_patch: false,
+            semver_strict: false,
+            min_feats_for_minor: 1,
+            reset_pre_release: false,
+            timeout: None,
+            pre_release_style: PreReleaseStyle::SemVer,
+            branch_tags_only: false,
+            release_tags: ReleaseTags::Any,
+            author_stats: false,
+            authors: HashMap::new(),
+            since_release: None,
+        }
+    }
+
+    /// Create a new VersionCalculator struct from the release state
+    ///
+    /// The current version is read from the state instead of the version
+    /// tags and the commits after the commit in the state are walked.
+    ///
+    /// ## Parameters
+    ///
+    ///  - path - the path to the repository
+    ///  - state - the release state e.g. read from a state file
+    ///
+    pub fn from_state<P: AsRef<Path>>(path: P, state: &State) -> Result<VersionCalculator, Error> {
+        let repo_path = path.as_ref().to_path_buf();
+        Repository::discover(&repo_path)?;
+        let current_version = state.semantic()?;
+        log::debug!("current version {} read from state", &current_version);
+        Ok(VersionCalculator::with_baseline(
+            repo_path,
+            current_version,
+            Baseline::Untagged,
+            state.commit()?,
+        ))
+    }
+
+    /// Report the version prefix set in the repository git config
+    ///
+    /// The prefix is read from the `nextsv.tagPrefix` key. Returns None
+    /// if the key is not set.
+    ///
+    /// ## Parameters
+    ///
+    ///  - path - the path to the repository
+    ///
+    pub fn configured_prefix<P: AsRef<Path>>(path: P) -> Result<Option<String>, Error> {
+        let repo = Repository::discover(path)?;
+        let config = repo.config()?;
+        match config.get_string(CONFIG_TAG_PREFIX) {
+            Ok(prefix) => {
+                log::debug!("{} found in git config: {:?}", CONFIG_TAG_PREFIX, &prefix);
+                Ok(Some(prefix))
+            }
+            Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// List every tag in the repository at a path
+    ///
+    /// Each tag is reported with how it was treated when looking for
+    /// version tags with the prefix and style given. The latest version
+    /// tag is marked. Tags are listed in name order.
+    ///
+    /// ## Error Handling
+    ///
+    /// Errors from 'git2' are returned.
+    ///
+    pub fn list_tags_in<P: AsRef<Path>>(
+        path: P,
+        version_prefix: &str,
+        style: PreReleaseStyle,
+        ignore_case: bool,
+    ) -> Result<Vec<TagListing>, Error> {
+        let repo = Repository::discover(path.as_ref())?;
+        let mut names = vec![];
+        for name in repo.tag_names(None)?.iter().flatten() {
+            names.push(name.to_string());
+        }
+        names.sort();
+        let mut listing: Vec<TagListing> = names
+            .into_iter()
+            .map(|name| {
+                let status = match matched_prefix(&name, version_prefix, ignore_case) {
+                    Some(prefix) => match Semantic::parse_with_style(&name, prefix, style) {
+                        Ok(version) => TagStatus::Parsed(version),
+                        Err(e) => TagStatus::Unparsed(e.to_string()),
+                    },
+                    None => TagStatus::NoPrefix,
+                };
+                TagListing {
+                    name,
+                    status,
+                    latest: false,
+                }
+            })
+            .collect();
+        let latest = listing
+            .iter()
+            .enumerate()
+            .filter_map(|(index, tag)| match &tag.status {
+                TagStatus::Parsed(version) => Some((index, version)),
+                _ => None,
+            })
+            .max_by(|(_, a), (_, b)| a.cmp(b))
+            .map(|(index, _)| index);
+        if let Some(index) = latest {
+            listing[index].latest = true;
+        }
+        Ok(listing)
+    }
+
+    /// Check every version tag in the repository at a path
+    ///
+    /// Reports the tags starting with the prefix that failed to parse,
+    /// the versions tagged on different commits, the gaps between
+    /// releases (e.g. 1.0.0 followed by 1.2.0), the releases tagged on an
+    /// ancestor of the commit tagged with the release before them and the
+    /// pre-releases that were never released.
+    ///
+    /// ## Error Handling
+    ///
+    /// Errors from 'git2' are returned.
+    ///
+    pub fn audit_tags_in<P: AsRef<Path>>(
+        path: P,
+        version_prefix: &str,
+        style: PreReleaseStyle,
+        ignore_case: bool,
+    ) -> Result<TagAudit, Error> {
+        let repo = Repository::discover(path.as_ref())?;
+        let (versions, skipped) = scan_tags(&repo, version_prefix, style, ignore_case, None)?;
+        let mut issues: Vec<TagIssue> = skipped
+            .into_iter()
+            .map(|(name, e)| TagIssue::Unparsed(name, e.to_string()))
+            .collect();
+        issues.extend(
+            ambiguous_tags(&versions)
+                .into_iter()
+                .map(|(version, first, second)| TagIssue::Ambiguous(version, first, second)),
+        );
+        issues.extend(audit_versions(&repo, &versions));
+        Ok(TagAudit {
+            versions: sorted_versions(versions).len(),
+            issues,
+        })
+    }
+
+    /// Report the current_version
+    ///
+    pub fn name(&self) -> Semantic {
+        self.current_version.clone()
+    }
+
+    /// Report the name of the tag used as the baseline
+    ///
+    pub fn baseline_tag(&self) -> String {
+        self.tag_name(&self.current_version)
+    }
+
+    /// The name of the tag for the version, following the tag template or
+    /// the tag glob if set
+    ///
+    /// A version parsed from a tag is named by the tag as written. A
+    /// pre-release the template cannot name is named by the glob of the
+    /// template as it is found when scanning the tags.
+    ///
+    fn tag_name(&self, version: &Semantic) -> String {
+        if let Some(tag) = version.original_tag() {
+            return tag.to_string();
+        }
+        if let Some(Ok(name)) = self.tag_template.as_ref().map(|t| t.tag_name(version)) {
+            return name;
+        }
+        match &self.tag_glob {
+            Some(glob) => glob.tag_name(version),
+            None => version.tag_name(),
+        }
+    }
+
+    /// Scan the tags for the version prefix and pre-release style
+    ///
+    #[allow(clippy::type_complexity)]
+    fn scan_tags(
+        &self,
+        repo: &Repository,
+    ) -> Result<(Vec<(Semantic, Option<git2::Oid>)>, Vec<SkippedTag>), Error> {
+        scan_tags(
+            repo,
+            self.current_version.version_prefix(),
+            self.pre_release_style,
+            self.prefix_ignore_case,
+            self.tag_glob.as_ref(),
+        )
+    }
+
+    /// Report the versions tagged in order from the lowest
+    ///
+    /// A version tagged more than once (e.g. `V1.2.3` and `v1.2.3` when
+    /// ignoring the case of the prefix) is listed once.
+    ///
+    /// ## Error Handling
+    ///
+    /// Errors from 'git2' are returned.
+    ///
+    pub fn all_versions(&self) -> Result<Vec<Semantic>, Error> {
+        let repo = self.repository()?;
+        let (versions, _) = self.scan_tags(&repo)?;
+        Ok(sorted_versions(versions))
+    }
+
+    /// Report the tags matching the version prefix that were skipped
+    ///
+    /// Tags are skipped when they start with the version prefix and a
+    /// number but cannot be parsed as a semantic version. Each tag is
+    /// reported with the parse error.
+    ///
+    /// ## Error Handling
+    ///
+    /// Errors from 'git2' are returned.
+    ///
+    pub fn skipped_tags(&self) -> Result<Vec<SkippedTag>, Error> {
+        let repo = self.repository()?;
+        let (_, skipped) = self.scan_tags(&repo)?;
+        Ok(skipped)
+    }
+
+    /// Check that no version is tagged on different commits
+    ///
+    /// Different tags can parse to the same version (e.g. `V1.2.3` and
+    /// `v1.2.3` when ignoring the case of the prefix). If they point at
+    /// different commits the release history is ambiguous.
+    ///
+    /// ## Error
+    ///
+    /// Report `Error::AmbiguousTag` for the first version found tagged
+    /// on different commits.
+    ///
+    pub fn check_tags(&self) -> Result<(), Error> {
+        let repo = self.repository()?;
+        let (versions, _) = self.scan_tags(&repo)?;
+        match ambiguous_tags(&versions).into_iter().next() {
+            Some((version, first, second)) => Err(Error::AmbiguousTag(version, first, second)),
+            None => Ok(()),
+        }
+    }
+
+    /// Check the answer does not duplicate or regress an existing tag
+    ///
+    /// When a change is reported the version number must be greater than
+    /// every existing version tag. No check is made when there is no change
+    /// or for a pre-release when the counter is reset. When only the tags
+    /// on the branch of HEAD are used the version number must not be an
+    /// existing version, as a release branch may follow higher versions.
+    ///
+    /// ## Error
+    ///
+    /// Report `Error::VersionRegression` naming the existing tag if the
+    /// version number is not greater than it.
+    ///
+    pub fn check_regression(&self, answer: &Answer) -> Result<(), Error> {
+        if answer.bump_level == Level::None {
+            return Ok(());
+        }
+        if self.reset_pre_release && answer.version_number.is_pre_release() {
+            log::debug!("pre-release counter reset so existing tags are not checked");
+            return Ok(());
+        }
+        let repo = self.repository()?;
+        let (versions, _) = self.scan_tags(&repo)?;
+        let mut versions: Vec<Semantic> =
+            versions.into_iter().map(|(version, _)| version).collect();
+        if self.branch_tags_only {
+            versions.retain(|version| *version == answer.version_number);
+        }
+        match regression(&answer.version_number, &versions) {
+            Some(existing) => Err(Error::VersionRegression(
+                answer.version_number.to_string(),
+                existing.to_string(),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Verify the latest version tag against its commits
+    ///
+    /// The next version is calculated from the previous version tag
+    /// using the commits up to the latest version tag and compared with
+    /// the latest version. Pre-release tags are not verified. If there
+    /// is no previous version tag there is nothing to verify and the
+    /// latest version is reported.
+    ///
+    /// ## Error
+    ///
+    /// Report `Error::VerifyMismatch` if the latest version tag does not
+    /// match the version calculated.
+    ///
+    pub fn verify(&self) -> Result<Answer, Error> {
+        let repo = self.repository()?;
+        let (versions, _) = self.scan_tags(&repo)?;
+        let versions: Vec<Semantic> = sorted_versions(versions)
+            .into_iter()
+            .filter(|version| !version.is_pre_release())
+            .collect();
+
+        let (previous, latest) = match versions.as_slice() {
+            [.., previous, latest] => (previous.clone(), latest.clone()),
+            [latest] => {
+                log::info!("no previous version tag so {} not verified", latest);
+                return Ok(self.answer(Level::None, latest.clone()));
+            }
+            [] => return Err(Error::NoVersionTag),
+        };
+        log::debug!("verifying {} from {}", &latest, &previous);
+
+        let mut calculator = self.clone();
+        calculator.baseline_oid = tag_commit(&repo, &self.tag_name(&previous));
+        calculator.current_version = previous;
+        calculator.baseline = Baseline::Tagged;
+        calculator.base_ref = None;
+        calculator.merge_base = None;
+        calculator.walk_from = tag_commit(&repo, &self.tag_name(&latest));
+        let answer = calculator.walk_commits()?.compute();
+
+        if answer.version_number == latest {
+            Ok(answer)
+        } else {
+            Err(Error::VerifyMismatch(
+                latest.to_string(),
+                answer.version_number.to_string(),
+                answer.bump_level.to_string(),
+            ))
+        }
+    }
+
+    /// Report the candidate next versions at each level (e.g. `v1.2.4`,
+    /// `v1.3.0` and `v2.0.0` for `v1.2.3`)
+    ///
+    /// The commits are not needed.
+    ///
+    pub fn previews(&self) -> Previews {
+        let mut patch = self.current_version.clone();
+        patch.increment_patch();
+        let mut minor = self.current_version.clone();
+        minor.increment_minor();
+        let mut major = self.current_version.clone();
+        major.increment_major();
+        Previews {
+            patch,
+            minor,
+            major,
+            initial_development: self.current_version.major() == 0 && !self.semver_strict,
+        }
+    }
+
+    /// Report the abbreviated id of the commit at HEAD
+    ///
+    /// Without an abbreviation the length follows git's rules (including
+    /// `core.abbrev`) so the id is unique in the repository.
+    ///
+    /// ## Parameters
+    ///
+    /// - abbrev - the number of hex characters to report (4 to 40)
+    ///
+    /// ## Error
+    ///
+    /// Report `Error::InvalidAbbrev` if the abbreviation is not between
+    /// 4 and 40. Errors from 'git2' are returned.
+    ///
+    pub fn head_short_id(&self, abbrev: Option<usize>) -> Result<String, Error> {
+        let repo = self.repository()?;
+        let head = repo.head()?.peel_to_commit()?;
+        match abbrev {
+            Some(abbrev) if (4..=40).contains(&abbrev) => {
+                Ok(head.id().to_string()[..abbrev].to_string())
+            }
+            Some(abbrev) => Err(Error::InvalidAbbrev(abbrev)),
+            None => Ok(head
+                .as_object()
+                .short_id()?
+                .as_str()
+                .unwrap_or_default()
+                .to_string()),
+        }
+    }
+
+    /// Report the name of the branch checked out at HEAD for the
+    /// features that depend on the branch
+    ///
+    /// CI checkouts are often detached. With a detached HEAD there is no
+    /// branch so the abbreviated id of the HEAD commit is reported
+    /// instead (as `head_short_id` without an abbreviation).
+    ///
+    /// ## Error Handling
+    ///
+    /// Errors from 'git2' are returned.
+    ///
+    pub fn head_branch(&self) -> Result<String, Error> {
+        let repo = self.repository()?;
+        if repo.head_detached()? {
+            log::debug!("HEAD is detached so the short id is used for the branch");
+            return self.head_short_id(None);
+        }
+        let head = repo.head()?;
+        Ok(head.shorthand().unwrap_or_default().to_string())
+    }
+
+    /// Report the number of commits walked from HEAD to the baseline
+    ///
+    /// Every commit walked is counted, including the commits that are
+    /// not conventional or are skipped. Zero until `walk_commits` is
+    /// called.
+    ///
+    pub fn distance(&self) -> u32 {
+        self.distance
+    }
+
+    /// Report the number of commits walked that are conventional commits
+    ///
+    /// Conventional commits that are ignored or not allowed are counted.
+    /// Zero until `walk_commits` is called.
+    ///
+    pub fn conventional_count(&self) -> usize {
+        match &self.conventional {
+            Some(conventional) => conventional.conventional_count(),
+            None => 0,
+        }
+    }
+
+    /// Report the number of commits walked by each author
+    ///
+    /// The author is named by the name of the commit author, or the
+    /// email if the name is not valid UTF-8. Empty unless `author_stats`
+    /// is set and `walk_commits` is called.
+    ///
+    pub fn authors(&self) -> &HashMap<String, u32> {
+        &self.authors
+    }
+
+    /// Report the id of the commit the walk started from
+    ///
+    /// The commit is HEAD unless verifying the latest version tag. None
+    /// until `walk_commits` is called.
+    ///
+    pub fn head_oid(&self) -> Option<git2::Oid> {
+        self.head_oid
+    }
+
+    /// Describe HEAD in the style of `git describe` (e.g. v1.2.3-5-gabcdef1)
+    ///
+    /// The baseline tag is followed by the distance and the abbreviated
+    /// HEAD commit id.
+    ///
+    /// ## Error Handling
+    ///
+    /// Errors from 'git2' are returned.
+    ///
+    pub fn describe(&self, abbrev: Option<usize>) -> Result<String, Error> {
+        Ok(format!(
+            "{}-{}-g{}",
+            self.baseline_tag(),
+            self.distance,
+            self.head_short_id(abbrev)?
+        ))
+    }
+
+    /// Report the URL comparing the baseline tag with the tag for the
+    /// next version (e.g. `https://github.com/owner/repo/compare/v1.2.3...v1.3.0`)
+    ///
+    /// The base is the URL of the repository on the forge. Returns None
+    /// if the baseline is not tagged (e.g. the initial release) as there
+    /// is nothing to compare with.
+    ///
+    /// ## Error Handling
+    ///
+    /// Errors from 'git2' are returned.
+    ///
+    pub fn compare_url(&self, base: &str, next: &Semantic) -> Result<Option<String>, Error> {
+        let repo = self.repository()?;
+        let previous = self.baseline_tag();
+        if repo
+            .find_reference(&format!("refs/tags/{}", previous))
+            .is_err()
+        {
+            log::debug!("no tag {} to compare with", previous);
+            return Ok(None);
+        }
+        Ok(Some(format!(
+            "{}/compare/{}...{}",
+            base.trim_end_matches('/'),
+            previous,
+            self.tag_name(next)
+        )))
+    }
+
+    /// Report the date of the HEAD commit (e.g. 2023-01-21)
+    ///
+    /// The date is in the time zone the commit was made in.
+    ///
+    /// ## Error Handling
+    ///
+    /// Errors from 'git2' are returned.
+    ///
+    pub fn head_date(&self) -> Result<String, Error> {
+        let repo = self.repository()?;
+        let time = repo.head()?.peel_to_commit()?.time();
+        let seconds = time.seconds() + i64::from(time.offset_minutes()) * 60;
+        Ok(civil_date(seconds.div_euclid(86_400)))
+    }
+
+    /// Report the release state for the answer released at HEAD
+    ///
+    /// ## Error Handling
+    ///
+    /// Errors from 'git2' are returned.
+    ///
+    pub fn release_state(&self, answer: &Answer) -> Result<State, Error> {
+        let repo = self.repository()?;
+        let head = repo.head()?.peel_to_commit()?;
+        Ok(State::new(&answer.version_number, Some(head.id())))
+    }
+
+    /// The name of the tag `create_tag` creates for the version
+    ///
+    /// The tag is named by the tag template if set, otherwise by the tag
+    /// glob or the version prefix.
+    ///
+    /// ## Error Handling
+    ///
+    /// Report `Error::PreReleaseNotInTemplate` if the tag template cannot
+    /// name the version.
+    ///
+    pub fn new_tag_name(&self, version: &Semantic) -> Result<String, Error> {
+        match &self.tag_template {
+            Some(template) => template.tag_name(version),
+            None => Ok(self.tag_name(version)),
+        }
+    }
+
+    /// Create a lightweight tag for the version at HEAD
+    ///
+    /// The tag is named by the tag template if set.
+    ///
+    /// ## Error Handling
+    ///
+    /// Report `Error::PreReleaseNotInTemplate` if the tag template cannot
+    /// name the version. Errors from 'git2' are returned, including when
+    /// the tag already exists.
+    ///
+    pub fn create_tag(&self, version: &Semantic) -> Result<git2::Oid, Error> {
+        let tag_name = self.new_tag_name(version)?;
+        let repo = self.repository()?;
+        let head = repo.head()?.peel_to_commit()?;
+        let oid = repo.tag_lightweight(&tag_name, head.as_object(), false)?;
+        log::info!("created tag {} at {}", tag_name, head.id());
+        Ok(oid)
+    }
+
+    /// Report the commit the baseline tag points at
+    ///
+    /// Returns None if the tag could not be resolved to a commit.
+    ///
+    pub fn baseline_oid(&self) -> Option<git2::Oid> {
+        self.baseline_oid
+    }
+
+    /// Report top level
+    ///
+    pub fn top_level(&self) -> Option<TypeHierarchy> {
+        if self.conventional.is_none() {
+            None
+        } else {
+            self.conventional.clone().unwrap().top_type()
+        }
+    }
+
+    /// The count of commits of a type in the conventional commits field
+    ///
+    /// ## Parameters
+    ///
+    /// - commit_type - identifies the type of commit e.g. "feat"
+    ///
+    /// ## Error handling
+    ///
+    /// If there are no conventional commits it returns 0.
+    /// If conventional is None returns 0.
+    ///
+    pub fn count_commits_by_type(&self, commit_type: &str) -> u32 {
+        match &self.conventional {
+            Some(conventional) => conventional.commits_by_type(commit_type),
+            None => 0_u32,
+        }
+    }
+
+    /// The counts of commits of each type in the conventional commits field
+    ///
+    /// ## Error handling
+    ///
+    /// If conventional is None returns an empty map.
+    ///
+    pub fn counts(&self) -> HashMap<String, u32> {
+        match &self.conventional {
+            Some(conventional) => conventional.counts(),
+            None => HashMap::new(),
+        }
+    }
+
+    /// Report the commits found by walking the commits
+    ///
+    /// ## Error handling
+    ///
+    /// If conventional is None there are no commits.
+    ///
+    pub fn commits(&self) -> &[ParsedCommit] {
+        match &self.conventional {
+            Some(conventional) => conventional.commits(),
+            None => &[],
+        }
+    }
+
+    /// Report the status of the breaking flag in the conventional commits
+    ///
+    /// ## Error Handling
+    ///
+    /// If the conventional is None returns false
+    ///
+    pub fn breaking(&self) -> bool {
+        match self.conventional.clone() {
+            Some(conventional) => conventional.breaking(),
+            None => false,
+        }
+    }
+
+    /// Summarise the commits found since the baseline tag
+    ///
+    /// For example: "12 commits since v1.2.3 (3 feat, 2 fix, 7 other; 1 breaking)".
+    /// Counts are zero if the commits have not been walked.
+    ///
+    pub fn range_summary(&self) -> String {
+        let (total, breaking) = match &self.conventional {
+            Some(conventional) => (conventional.commits_count(), conventional.breaking_count()),
+            None => (0, 0),
+        };
+        let feat = self.count_commits_by_type("feat") as usize;
+        let fix = self.count_commits_by_type("fix") as usize;
+        format!(
+            "{} {} since {} ({} feat, {} fix, {} other; {} breaking)",
+            total,
+            if total == 1 { "commit" } else { "commits" },
+            self.baseline_tag(),
+            feat,
+            fix,
+            total.saturating_sub(feat + fix),
+            breaking
+        )
+    }
+
+    /// Force update next_version to return a specific result
+    ///
+    /// Options are defined in `ForceLevel`
+    ///
+    pub fn force(&mut self, level: ForceLevel) -> Self {
+        let mut conventional_commits = ConventionalCommits::new();
+        log::debug!("forcing a change to {}", level);
+        conventional_commits.push_forced(&level);
+
+        self.conventional = Some(conventional_commits);
+        self.clone()
+    }
+
+    /// Read release directives from git notes in the notes ref
+    ///
+    /// The notes are read during `walk_commits`. A note on a commit can
+    /// override the level at which the commit is counted (`bump: major`)
+    /// or exclude the commit from the calculation (`skip: true`).
+    ///
+    /// ## Parameters
+    ///
+    /// - notes_ref - the notes ref e.g. "refs/notes/nextsv" or "nextsv"
+    ///
+    pub fn read_notes(mut self, notes_ref: &str) -> Self {
+        self.notes_ref = Some(notes::full_ref(notes_ref));
+        self
+    }
+
+    /// Only count breaking changes that have no scope or the scope given
+    ///
+    /// Breaking changes scoped to another area (e.g. another package in
+    /// a monorepo) are counted at the level of their type as if they
+    /// were not breaking.
+    ///
+    /// ## Parameters
+    ///
+    /// - scope - the scope of the package being versioned
+    ///
+    pub fn breaking_requires_scope(mut self, scope: &str) -> Self {
+        self.breaking_scope = Some(scope.to_string());
+        self
+    }
+
+    /// Only count the commits on HEAD that are not on the base ref
+    ///
+    /// The walk stops at the merge-base of HEAD and the base ref instead
+    /// of the version tag, so the bump reflects just the commits on the
+    /// branch (e.g. the commits in a pull request).
+    ///
+    /// ## Parameters
+    ///
+    /// - base_ref - the base branch or ref e.g. "origin/main"
+    ///
+    pub fn base_ref(mut self, base_ref: &str) -> Self {
+        self.base_ref = Some(base_ref.to_string());
+        self
+    }
+
+    /// Only count the commits on the tip ref that are not on the other ref
+    ///
+    /// The walk starts at the tip instead of HEAD and stops at the
+    /// merge-base of the two refs instead of the version tag (e.g. the
+    /// commits on a release train branch). Takes precedence over
+    /// `base_ref`.
+    ///
+    /// ## Parameters
+    ///
+    /// - other - the branch or ref the tip diverged from e.g. "main"
+    /// - tip - the branch or ref to count the commits on e.g. "release/1.x"
+    ///
+    pub fn merge_base(mut self, other: &str, tip: &str) -> Self {
+        self.merge_base = Some((other.to_string(), tip.to_string()));
+        self
+    }
+
+    /// Ignore the commit given and its ancestors
+    ///
+    /// The commit is a lower boundary for the walk in addition to the
+    /// version tag, e.g. the commit importing the history of another
+    /// repository. Referenced by id or any revision git understands.
+    ///
+    /// ## Parameters
+    ///
+    /// - rev - the baseline commit e.g. "1a2b3c4"
+    ///
+    pub fn baseline_commit(mut self, rev: &str) -> Self {
+        self.baseline_commit = Some(rev.to_string());
+        self
+    }
+
+    /// Ignore commits of the conventional types given
+    ///
+    /// Commits of these types are not counted when calculating the
+    /// next version.
+    ///
+    /// ## Parameters
+    ///
+    /// - types - the conventional types to ignore e.g. "chore"
+    ///
+    pub fn ignore_types(mut self, types: Vec<String>) -> Self {
+        self.ignore_types = types;
+        self
+    }
+
+    /// Skip the commits with a message containing one of the markers
+    ///
+    /// Skipped commits are not counted when calculating the next version
+    /// even if they are conventional. The default marker is
+    /// `[skip release]`. Empty markers are left out, so an empty list or
+    /// an empty marker skips no commits.
+    ///
+    /// ## Parameters
+    ///
+    /// - markers - the text marking a commit to skip e.g. "[skip ci]"
+    ///
+    pub fn skip_markers(mut self, markers: Vec<String>) -> Self {
+        self.skip_markers = markers.into_iter().filter(|m| !m.is_empty()).collect();
+        self
+    }
+
+    /// Count commits of the types given at most once
+    ///
+    /// However many commits of a collapsed type are found they count as
+    /// one commit towards the patch threshold and in the logging, so
+    /// twenty chores are counted the same as one.
+    ///
+    /// ## Parameters
+    ///
+    /// - types - the conventional types to collapse e.g. "chore"
+    ///
+    pub fn collapse_types(mut self, types: Vec<String>) -> Self {
+        self.collapse_types = types;
+        self
+    }
+
+    /// Only count commits of the types given
+    ///
+    /// Commits of other types are ignored in the calculation of the next
+    /// version (e.g. the types listed in a commitlint config).
+    ///
+    /// ## Parameters
+    ///
+    /// - types - the conventional types to count e.g. "feat"
+    ///
+    pub fn allowed_types(mut self, types: Vec<String>) -> Self {
+        self.allowed_types = Some(types);
+        self
+    }
+
+    /// Count a breaking change for commits that are not conventional but
+    /// have a `BREAKING CHANGE:` footer in the message
+    ///
+    pub fn breaking_footer_always(mut self) -> Self {
+        self.breaking_footer_always = true;
+        self
+    }
+
+    /// Set how dependency update commits (type or scope `deps`) are counted
+    ///
+    /// By default they are counted as a patch change. With `DepsBump::None`
+    /// they are ignored so that a series of automated dependency updates
+    /// does not require a release.
+    ///
+    pub fn deps_bump(mut self, deps_bump: DepsBump) -> Self {
+        self.deps_bump = deps_bump;
+        self
+    }
+
+    /// Set how revert commits (type `revert`) are counted
+    ///
+    /// By default they are counted as a fix. With `RevertBump::None` they
+    /// are ignored so that reverting a change does not require a release.
+    ///
+    pub fn revert_bump(mut self, revert_bump: RevertBump) -> Self {
+        self.revert_bump = revert_bump;
+        self
+    }
+
+    /// Set how conventional commits with a type that is not known (e.g.
+    /// `security:`) are counted
+    ///
+    /// By default they are counted as a patch change at the other level.
+    /// With `UnknownType::Ignore` they are ignored unless they are
+    /// breaking changes.
+    ///
+    pub fn unknown_type(mut self, unknown_type: UnknownType) -> Self {
+        self.unknown_type = unknown_type;
+        self
+    }
+
+    /// Count the commits of the type at the level given
+    ///
+    /// By default `perf`, `docs`, `style`, `refactor`, `test`, `chore`,
+    /// `build` and `ci` are counted at the other level, `fix` and `revert`
+    /// at the fix level and `feat` at the feature level (see
+    /// `TypeHierarchy::parse`). Commits of a type at the feature level
+    /// bump the minor number, so `perf` can be set to
+    /// `TypeHierarchy::Feature` to release performance improvements as a
+    /// minor change. Only breaking changes bump the major number so
+    /// `TypeHierarchy::Breaking` is counted as the feature level.
+    ///
+    pub fn type_level(mut self, type_: &str, level: TypeHierarchy) -> Self {
+        let level = match level {
+            TypeHierarchy::Breaking => TypeHierarchy::Feature,
+            level => level,
+        };
+        self.type_levels.insert(type_.to_lowercase(), level);
+        self
+    }
+
+    /// Count the conventional commits listed as bullet lines in the body
+    /// of a squash commit as separate commits
+    ///
+    /// A squash merge commit usually lists the squashed commits in its
+    /// body (e.g. `- feat: add a feature`). Each bullet line that parses
+    /// as a conventional commit is counted in addition to the summary.
+    ///
+    pub fn expand_squash_body(mut self) -> Self {
+        self.expand_squash_body = true;
+        self
+    }
+
+    /// Count the commits of each author during the walk
+    ///
+    /// The counts are reported by `authors` and in the metrics of the
+    /// release plan.
+    ///
+    pub fn author_stats(mut self) -> Self {
+        self.author_stats = true;
+        self
+    }
+
+    /// Bump the patch number for every change while the major number is 0
+    ///
+    /// Before 1.0.0 a feature already bumps the patch number and a
+    /// breaking change bumps the minor number. With this option a breaking
+    /// change (including a forced major change) also bumps the patch number.
+    /// The option has no effect from 1.0.0.
+    ///
+    pub fn zero_feat_as_patch(mut self) -> Self {
+        self.zero_feat_as_patch = true;
+        self
+    }
+
+    /// Apply the same rules while the major number is 0 as from 1.0.0
+    ///
+    /// A breaking change bumps the major number and a feature the minor
+    /// number, so a breaking change to `v0.3.0` gives `v1.0.0`. Takes
+    /// precedence over `zero_feat_as_patch`.
+    ///
+    pub fn semver_strict(mut self) -> Self {
+        self.semver_strict = true;
+        self
+    }
+
+    /// Require at least this many feature commits to bump the minor number
+    ///
+    /// With fewer feature commits the change is counted as a patch. The
+    /// default is 1 and 0 is treated as 1.
+    ///
+    pub fn min_feats_for_minor(mut self, count: u32) -> Self {
+        self.min_feats_for_minor = count.max(1);
+        self
+    }
+
+    /// Restart the pre-release series at 1 whatever the existing tags
+    ///
+    /// `next_pre_release` reports the first pre-release for the label
+    /// (e.g. `v1.2.0-rc.1` after `v1.2.0-rc.5` was abandoned) and the
+    /// pre-release is not checked against the existing tags.
+    ///
+    pub fn reset_pre_release(mut self) -> Self {
+        self.reset_pre_release = true;
+        self
+    }
+
+    /// Stop walking the commits if the walk takes longer than the timeout
+    ///
+    /// The time taken is checked every 100 commits and `walk_commits`
+    /// reports `Error::WalkTimedOut` once the timeout is exceeded.
+    ///
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Parse and write pre-release suffixes in the style given
+    ///
+    /// The version tags are scanned again to find the current version
+    /// using the style.
+    ///
+    /// ## Error Handling
+    ///
+    /// Errors from 'git2' are returned and `Error::NoVersionTag` if no
+    /// version tag is found.
+    ///
+    pub fn pre_release_style(mut self, style: PreReleaseStyle) -> Result<Self, Error> {
+        self.pre_release_style = style;
+        if self.baseline == Baseline::Latest {
+            self.rescan_latest()?;
+        } else {
+            self.current_version.set_pre_release_style(style);
+        }
+        Ok(self)
+    }
+
+    /// Only use the version tags on the commits reachable from HEAD
+    ///
+    /// Tags on other branches (e.g. other release branches) are not
+    /// considered when finding the latest version, so the version tags are
+    /// scanned again.
+    ///
+    /// ## Error Handling
+    ///
+    /// Errors from 'git2' are returned and `Error::NoVersionTag` if no
+    /// version tag is found on the branch.
+    ///
+    pub fn branch_tags_only(mut self) -> Result<Self, Error> {
+        self.branch_tags_only = true;
+        if self.baseline == Baseline::Latest {
+            self.rescan_latest()?;
+        }
+        Ok(self)
+    }
+
+    /// Only use the version tags of the kind given as the baseline
+    ///
+    /// For example lightweight tags can mark development milestones while
+    /// annotated tags mark the releases. By default both kinds are used.
+    /// The version tags are scanned again.
+    ///
+    /// ## Error Handling
+    ///
+    /// Errors from 'git2' are returned and `Error::NoVersionTag` if no
+    /// version tag of the kind is found.
+    ///
+    pub fn release_tags(mut self, release_tags: ReleaseTags) -> Result<Self, Error> {
+        self.release_tags = release_tags;
+        if self.baseline == Baseline::Latest {
+            self.rescan_latest()?;
+        }
+        Ok(self)
+    }
+
+    /// Find the latest version again with the settings of the calculator
+    ///
+    /// Tags on other branches are left out if only the tags on the branch
+    /// of HEAD are used, and tags of another kind than the release tags.
+    ///
+    fn rescan_latest(&mut self) -> Result<(), Error> {
+        let repo = self.repository()?;
+        let prefix = self.current_version.version_prefix().to_string();
+        let head = if self.branch_tags_only {
+            Some(repo.head()?.peel_to_commit()?.id())
+        } else {
+            None
+        };
+        let (versions, _) = scan_tags(
+            &repo,
+            &prefix,
+            self.pre_release_style,
+            self.prefix_ignore_case,
+            self.tag_glob.as_ref(),
+        )?;
+        let versions = versions
+            .into_iter()
+            .filter(|(version, commit)| {
+                if let Some(head) = head {
+                    if !on_branch(&repo, head, *commit) {
+                        log::debug!("version {} is not tagged on the branch of HEAD", version);
+                        return false;
+                    }
+                }
+                let tag_name = self.tag_name(version);
+                if !self.release_tags.matches(&repo, &tag_name) {
+                    log::debug!("tag {} is not a release tag", tag_name);
+                    return false;
+                }
+                true
+            })
+            .collect();
+        self.current_version = latest_of(versions)?;
+        self.baseline_oid = tag_commit(&repo, &self.tag_name(&self.current_version));
+        Ok(())
+    }
+
+    /// Get the conventional commits created since the tag was created
+    ///
+    /// Uses `git2` to open the repository and walk back to the
+    /// latest version tag collecting the conventional commits.
+    ///
+    /// ## Error Handling
+    ///
+    /// Errors from 'git2' are returned and `Error::RepositoryBusy` if a
+    /// rebase or bisect is in progress.
+    ///
+    pub fn walk_commits(mut self) -> Result<Self, Error> {
+        let repo = self.repository()?;
+        log::debug!("repo opened to find conventional commits");
+        check_state(repo.state())?;
+        let mut revwalk = repo.revwalk()?;
+        revwalk.set_sorting(git2::Sort::NONE)?;
+        let head_oid = match (self.walk_from, &self.merge_base) {
+            (Some(oid), _) => {
+                log::debug!("starting the walk from {}", oid);
+                oid
+            }
+            (None, Some((_, tip))) => {
+                log::debug!("starting the walk from the tip {}", tip);
+                repo.revparse_single(tip)?.peel_to_commit()?.id()
+            }
+            (None, None) => {
+                log::debug!("starting the walk from the HEAD");
+                repo.head()?.peel_to_commit()?.id()
+            }
+        };
+        revwalk.push(head_oid)?;
+        match (&self.merge_base, &self.base_ref) {
+            (Some((other, tip)), _) => {
+                let other_oid = repo.revparse_single(other)?.peel_to_commit()?.id();
+                let merge_base = repo.merge_base(other_oid, head_oid)?;
+                revwalk.hide(merge_base)?;
+                log::debug!(
+                    "hide commits from merge-base {} of {} and {}",
+                    merge_base,
+                    other,
+                    tip
+                );
+            }
+            (None, Some(base_ref)) => {
+                let base = repo.revparse_single(base_ref)?.peel_to_commit()?;
+                let head = repo.head()?.peel_to_commit()?;
+                let merge_base = repo.merge_base(head.id(), base.id())?;
+                revwalk.hide(merge_base)?;
+                log::debug!(
+                    "hide commits from merge-base {} with {}",
+                    merge_base,
+                    base_ref
+                );
+            }
+            (None, None) if self.baseline != Baseline::Untagged => {
+                let glob = format!("refs/tags/{}", self.baseline_tag());
+                revwalk.hide_ref(&glob)?;
+                log::debug!("hide commits from {}", &self.current_version);
+            }
+            (None, None) => match self.baseline_oid {
+                Some(oid) => {
+                    revwalk.hide(oid)?;
+                    log::debug!("hide commits from {} in the state", oid);
+                }
+                None => log::debug!("no commit in the state so walking all commits"),
+            },
+        }
+        if let Some(rev) = &self.baseline_commit {
+            let baseline = repo.revparse_single(rev)?.peel_to_commit()?;
+            revwalk.hide(baseline.id())?;
+            log::debug!("hide commits from baseline commit {}", baseline.id());
+        }
+
+        macro_rules! filter_try {
+            ($e:expr) => {
+                match $e {
+                    Ok(t) => t,
                     Err(e) => return Some(Err(e)),
                 }
             };
@@ -246,55 +2108,188 @@ impl VersionCalculator {
         });
 
         let mut conventional_commits = ConventionalCommits::new();
+        conventional_commits.set_breaking_scope(self.breaking_scope.clone());
+        conventional_commits.set_ignore_types(self.ignore_types.clone());
+        conventional_commits.set_collapse_types(self.collapse_types.clone());
+        conventional_commits.set_allowed_types(self.allowed_types.clone());
+        conventional_commits.set_breaking_footer_always(self.breaking_footer_always);
+        conventional_commits.set_deps_bump(self.deps_bump);
+        conventional_commits.set_revert_bump(self.revert_bump);
+        conventional_commits.set_unknown_type(self.unknown_type);
+        conventional_commits.set_type_levels(self.type_levels.clone());
+        conventional_commits.set_expand_squash_body(self.expand_squash_body);
 
         // Walk back through the commits
+        let started = Instant::now();
         let mut files = HashSet::new();
-        for commit in revwalk.flatten() {
+        let mut authors = HashMap::new();
+        let mut distance = 0;
+        for (count, commit) in revwalk.flatten().enumerate() {
+            distance += 1;
+            if self.author_stats {
+                let author = commit.author();
+                let name = author
+                    .name()
+                    .or_else(|| author.email())
+                    .unwrap_or("unknown");
+                *authors.entry(name.to_string()).or_insert(0) += 1;
+            }
+            if let Some(timeout) = self.timeout {
+                if count % TIMEOUT_CHECK_INTERVAL == 0 && started.elapsed() >= timeout {
+                    log::debug!("walk timed out after {} commits", count);
+                    return Err(Error::WalkTimedOut(timeout));
+                }
+            }
             // Get the summary for the conventional commits vec
             log::trace!("commit found: {}", &commit.summary().unwrap_or_default());
-            conventional_commits.push(&commit);
-            // Get the files for the files vec
+            let directives = match &self.notes_ref {
+                Some(notes_ref) => Directives::read(&repo, notes_ref, commit.id()),
+                None => Directives::default(),
+            };
+            let message = commit.message().unwrap_or_default();
+            if directives.skip {
+                log::debug!("commit skipped by note: {}", commit.id());
+            } else if let Some(marker) = self.skip_markers.iter().find(|m| message.contains(*m)) {
+                log::debug!("commit skipped by marker {}: {}", marker, commit.id());
+            } else if let Some(level) = directives.bump {
+                log::debug!("commit counted at {} by note: {}", level, commit.id());
+                conventional_commits.push_at_level(&commit, &level);
+            } else {
+                conventional_commits.push(&commit);
+            }
+            // Get the files changed by the commit for the files vec
             let tree = commit.tree()?;
-            let diff = repo.diff_tree_to_workdir(Some(&tree), None).unwrap();
+            let parent_tree = match commit.parent(0) {
+                Ok(parent) => Some(parent.tree()?),
+                Err(_) => None,
+            };
+            let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)?;
 
             diff.print(git2::DiffFormat::NameOnly, |delta, _hunk, _line| {
-                let file = delta.new_file().path().unwrap().file_name().unwrap();
-                log::trace!("file found: {:?}", file);
-                files.insert(file.to_os_string());
+                if let Some(file) = delta.new_file().path().and_then(|p| p.file_name()) {
+                    log::trace!("file found: {:?}", file);
+                    files.insert(file.to_os_string());
+                }
                 true
-            })
-            .unwrap();
+            })?;
         }
 
+        if self.current_version.is_pre_release() {
+            self.since_release = self.walk_since_release(&repo)?;
+        }
         self.conventional = Some(conventional_commits);
+        self.distance = distance;
+        self.authors = authors;
+        self.head_oid = Some(head_oid);
+        log::debug!("{} commits walked", distance);
         log::debug!("Files found: {:#?}", &files);
         self.files = Some(files);
 
         Ok(self)
     }
 
+    /// Open the repository used for the calculation
+    ///
+    pub fn repository(&self) -> Result<Repository, Error> {
+        Ok(Repository::discover(&self.repo_path)?)
+    }
+
+    /// Calculate the next version and pass the answer and the repository
+    /// to a callback before returning the answer.
+    ///
+    /// The callback is the place for side effects such as creating the
+    /// tag, updating files or sending notifications. The library does not
+    /// perform any of these actions itself.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), nextsv::Error> {
+    /// use nextsv::VersionCalculator;
+    ///
+    /// let calculator = VersionCalculator::new("v")?.walk_commits()?;
+    /// let answer = calculator.on_result(|answer, repo| {
+    ///     if let Ok(head) = repo.head() {
+    ///         println!("{} calculated at {:?}", answer.version_number, head.target());
+    ///     }
+    /// })?;
+    /// println!("Next Level: {}", answer.bump_level);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// ## Error Handling
+    ///
+    /// Errors from 'git2' opening the repository are returned and the
+    /// callback is not called.
+    ///
+    pub fn on_result<F>(&self, callback: F) -> Result<Answer, Error>
+    where
+        F: FnOnce(&Answer, &Repository),
+    {
+        let answer = self.compute();
+        let repo = self.repository()?;
+        log::debug!("calling the result callback");
+        callback(&answer, &repo);
+        Ok(answer)
+    }
+
     /// Calculate the next version and report the version number
     /// and level at which the change is made.
+    ///
+    /// Delegates to `compute`.
     pub fn next_version(&mut self) -> Answer {
+        self.compute()
+    }
+
+    /// Calculate the next version and report the version number
+    /// and level at which the change is made without changing
+    /// the calculator.
+    ///
+    /// If the current version is a pre-release the core version is
+    /// calculated as for `next_pre_release` and released, so a fix after
+    /// `v1.3.0-rc.1` reports `v1.3.0` while a breaking change reports
+    /// `v2.0.0`. The release before the pre-release is found by
+    /// `walk_commits`, so the repository is not opened here.
+    ///
+    pub fn compute(&self) -> Answer {
+        let bump = self.bump_level();
+        let next_version = if self.current_version.is_pre_release() && bump != Level::None {
+            self.pre_release_core()
+        } else {
+            next_version_calculator(self.current_version.clone(), &bump)
+        };
+
+        self.answer(bump, next_version)
+    }
+
+    /// Report the level at which the next change is made
+    ///
+    /// The level is calculated as for `compute`, including the rules
+    /// applied while the major number is 0, without calculating the next
+    /// version number. Reports `Level::None` if no conventional commits
+    /// have been collected.
+    ///
+    pub fn bump_level(&self) -> Level {
         // check the conventional commits. No conventional commits; no change.
         #[cfg(let_else)]
-        let Some(conventional) = self.conventional.clone() else {
-            return Answer::new(Level::None , self.current_version.clone(), None)
+        let Some(conventional) = self.conventional.as_ref() else {
+            return Level::None;
         };
         #[cfg(not(let_else))]
-        let conventional = match self.conventional.clone() {
+        let conventional = match self.conventional.as_ref() {
             Some(c) => c,
-            None => return Answer::new(Level::None, self.current_version.clone(), None),
+            None => return Level::None,
         };
 
         let bump = if conventional.breaking() {
             // Breaking change found in commits
             log::debug!("breaking change found");
             Level::Major
-        } else if 0 < conventional.commits_by_type("feat") {
+        } else if self.min_feats_for_minor <= conventional.feature_count() {
             log::debug!(
                 "{} feature commit(s) found requiring increment of minor number",
-                &conventional.commits_by_type("feat")
+                &conventional.feature_count()
             );
             Level::Minor
         } else if 0 < conventional.commits_all_types() {
@@ -307,19 +2302,193 @@ impl VersionCalculator {
             Level::None
         };
 
-        let final_bump = if self.current_version.major() == 0 {
+        if self.current_version.major() == 0 && self.semver_strict {
+            log::debug!("not yet at a stable version but applying the rules from 1.0.0");
+            bump
+        } else if self.current_version.major() == 0 {
             log::info!("Not yet at a stable version");
             match bump {
+                Level::Major if self.zero_feat_as_patch => {
+                    log::debug!("breaking change counted as patch before 1.0.0");
+                    Level::Patch
+                }
                 Level::Major => Level::Minor,
                 Level::Minor => Level::Patch,
                 _ => bump,
             }
         } else {
             bump
+        }
+    }
+
+    /// Calculate the next version and start or continue a pre-release
+    /// series for it
+    ///
+    /// The core version is bumped first and then the first pre-release
+    /// for the label is attached, so a minor change to `v1.2.0` with the
+    /// label "rc" reports `v1.3.0-rc.1` at level minor.
+    ///
+    /// If the current version is a pre-release the core version is
+    /// calculated again from the commits since the last release. If the
+    /// core is unchanged the series for the label continues from the
+    /// highest existing tag for the core and label, so with
+    /// `v1.2.0-alpha.3` and `v1.2.0-beta.1` tagged the label "beta"
+    /// reports `v1.2.0-beta.2`. If the commits change the core (e.g. a
+    /// breaking change after `v1.2.0-rc.3` released from `v1.1.0`) a
+    /// new series is started (`v2.0.0-rc.1`).
+    ///
+    /// If there is no change the current version is reported unchanged.
+    ///
+    /// ## Error
+    ///
+    /// Report error if the label is not a valid pre-release identifier or
+    /// the pre-release is not greater than the current version (e.g.
+    /// returning to alpha after beta).
+    ///
+    pub fn next_pre_release(&mut self, label: &str) -> Result<Answer, Error> {
+        let reset = self.reset_pre_release;
+        self.pre_release_answer(|core, existing| {
+            let mut version = pre_release_version(core, label, existing)?;
+            if reset {
+                log::debug!("pre-release counter reset");
+                version.reset_pre_release_counter()?;
+            }
+            Ok(version)
+        })
+    }
+
+    /// Calculate the next version and attach a pre-release with the label
+    /// and the counter given
+    ///
+    /// The core version is calculated as for `next_pre_release` but the
+    /// counter is not found from the tags, so a build number from a CI run
+    /// (e.g. 427) reports `v1.3.0-ci.427` for the label "ci".
+    ///
+    /// If there is no change the current version is reported unchanged.
+    ///
+    /// ## Error
+    ///
+    /// Report error if the label or the counter is not a valid pre-release
+    /// identifier or the pre-release is not greater than the current
+    /// version.
+    ///
+    pub fn next_pre_release_numbered(
+        &mut self,
+        label: &str,
+        counter: &str,
+    ) -> Result<Answer, Error> {
+        self.pre_release_answer(|mut core, _| {
+            core.set_pre_release(PreRelease::parse(&format!("{}.{}", label, counter))?);
+            Ok(core)
+        })
+    }
+
+    /// Calculate the next version and attach the pre-release made from the
+    /// core version and the existing version tags
+    ///
+    fn pre_release_answer<F>(&mut self, pre_release: F) -> Result<Answer, Error>
+    where
+        F: FnOnce(Semantic, &[Semantic]) -> Result<Semantic, Error>,
+    {
+        let answer = self.compute();
+        if answer.bump_level == Level::None {
+            log::debug!("no change so no pre-release started");
+            return Ok(answer);
+        }
+        let repo = self.repository()?;
+        let (existing, _) = self.scan_tags(&repo)?;
+        let existing: Vec<Semantic> = existing.into_iter().map(|(version, _)| version).collect();
+        let version = pre_release(answer.version_number, &existing)?;
+        if version <= self.current_version && !self.reset_pre_release {
+            return Err(Error::VersionNotGreater(
+                version.to_string(),
+                self.current_version.to_string(),
+            ));
+        }
+        log::debug!("next pre-release is {}", &version);
+        Ok(self.answer(answer.bump_level, version))
+    }
+
+    /// Find the last release before the current pre-release and the level
+    /// of the commits since it
+    ///
+    /// Called by `walk_commits` so the core version of the pre-release is
+    /// calculated without opening the repository again. Reports `None` if
+    /// there is no release before the pre-release.
+    ///
+    fn walk_since_release(&self, repo: &Repository) -> Result<Option<(Semantic, Level)>, Error> {
+        let current_core = self.current_version.truncate_to(Level::Patch);
+        let (existing, _) = self.scan_tags(repo)?;
+        let release = existing
+            .into_iter()
+            .map(|(version, _)| version)
+            .filter(|version| !version.is_pre_release() && *version < current_core)
+            .max();
+        let release = match release {
+            Some(release) => release,
+            None => {
+                log::debug!("no release before {}", &current_core);
+                return Ok(None);
+            }
         };
-        let next_version = next_version_calculator(self.current_version.clone(), &final_bump);
+        let mut since_release = self.clone();
+        since_release.baseline_oid = tag_commit(repo, &self.tag_name(&release));
+        since_release.current_version = release.clone();
+        since_release.baseline = Baseline::Tagged;
+        let since_release = since_release.walk_commits()?;
+        log::debug!(
+            "commits since release {} at level {}",
+            &release,
+            since_release.bump_level()
+        );
+        Ok(Some((release, since_release.bump_level())))
+    }
+
+    /// The core version for the next pre-release when the current version
+    /// is a pre-release
+    ///
+    /// The core version is calculated from the commits since the last
+    /// release found by `walk_commits`. The core of the current
+    /// pre-release is kept unless the core calculated is greater. If there
+    /// is no release the current pre-release is incremented, which keeps
+    /// the core unless it does not cover the change (e.g. a breaking change
+    /// after `v1.2.0-rc.1`).
+    ///
+    fn pre_release_core(&self) -> Semantic {
+        let current_core = self.current_version.truncate_to(Level::Patch);
+        let (release, level) = match &self.since_release {
+            Some(since_release) => since_release,
+            None => {
+                return next_version_calculator(self.current_version.clone(), &self.bump_level())
+            }
+        };
+        // a forced change applies from the release as well
+        let level = level.clone().max(self.bump_level());
+        let core = next_version_calculator(release.clone(), &level);
+        if core > current_core {
+            log::debug!(
+                "commits change the core from {} to {} so a new series is started",
+                &current_core,
+                &core
+            );
+            core
+        } else {
+            log::debug!("core {} unchanged by the commits", &current_core);
+            current_core
+        }
+    }
 
-        Answer::new(final_bump, next_version, None)
+    fn answer(&self, bump_level: Level, version_number: Semantic) -> Answer {
+        debug_assert!(
+            version_number >= self.current_version || self.reset_pre_release,
+            "next version {} is lower than the current version {}",
+            version_number,
+            self.current_version
+        );
+        let mut answer = Answer::new(bump_level, version_number, self.top_level());
+        answer.baseline_tag = self.baseline_tag();
+        answer.head_oid = self.head_oid.map(|oid| oid.to_string()).unwrap_or_default();
+        answer
     }
 
     /// Report version 1.0.0 and update level major
@@ -329,40 +2498,103 @@ impl VersionCalculator {
     /// Report error if major version number is greater than 0
     pub fn promote_first(&mut self) -> Result<Answer, Error> {
         if 0 < self.current_version.major() {
+            return Err(Error::MajorAlreadyUsed(
+                self.current_version.major().to_string(),
+            ));
+        }
+        self.force(ForceLevel::Major);
+        self.first_production()
+    }
+
+    /// Report the version given as the first production release and update
+    /// level major
+    ///
+    /// The version may be given with or without the version prefix.
+    ///
+    /// ## Parameters
+    ///
+    /// - version - the first production version e.g. "3.0.0"
+    ///
+    /// ## Error
+    ///
+    /// Report error if the version is not valid, is less than 1.0.0 or is
+    /// not greater than the current version.
+    pub fn promote_first_to(&mut self, version: &str) -> Result<Answer, Error> {
+        let prefix = self.current_version.version_prefix().to_string();
+        let tag = if version.starts_with(&prefix) {
+            version.to_string()
+        } else {
+            format!("{}{}", prefix, version)
+        };
+        let target = Semantic::parse(&tag, &prefix)?;
+        if target.major() < 1 {
+            return Err(Error::FirstProductionTooLow(target.to_string()));
+        }
+        if target <= self.current_version {
+            return Err(Error::VersionNotGreater(
+                target.to_string(),
+                self.current_version.to_string(),
+            ));
+        }
+        self.force(ForceLevel::Major);
+        log::debug!("promoting to first production release {}", &target);
+        Ok(self.answer(Level::Major, target))
+    }
+
+    /// Report version 1.0.0 and update level major if a breaking change
+    /// has been found, otherwise calculate the next version applying
+    /// the rules for versions before 1.0.0.
+    ///
+    /// ## Error
+    ///
+    /// Report error if major version number is greater than 0
+    pub fn promote_if_breaking(&self) -> Result<Answer, Error> {
+        if self.breaking() {
+            log::debug!("breaking change found so promoting to first production release");
+            self.first_production()
+        } else if 0 < self.current_version.major() {
             Err(Error::MajorAlreadyUsed(
                 self.current_version.major().to_string(),
             ))
         } else {
-            Ok(self.force(ForceLevel::Major).next_version())
+            log::debug!("no breaking change found so not promoting");
+            Ok(self.compute())
         }
     }
 
+    fn first_production(&self) -> Result<Answer, Error> {
+        let mut version = self.current_version.clone();
+        version.first_production()?;
+        Ok(self.answer(Level::Major, version))
+    }
+
     /// Check for required files
     ///
     /// ## Parameters
     ///
     /// - files - a list of the required files or None
     ///
+    /// The check is only applied when the highest type of conventional
+    /// commit found meets the level. If no conventional commits have been
+    /// found there is nothing to release and the check is not applied.
+    ///
     /// ## Error
     ///
-    /// Report error if one of the files are not found.
-    /// Exits on the first failure.
+    /// Report `Error::MissingRequiredFile` with the files not found, the
+    /// level enforced and the top type found.
+    /// Report error if the conventional commits have not been collected.
     pub fn has_required(
         &self,
         files_required: Vec<OsString>,
         level: TypeHierarchy,
     ) -> Result<(), Error> {
-        // How to use level to ensure that the rule is only applied
-        // when required levels of commits are included
-
-        if self
-            .conventional
-            .as_ref()
-            .unwrap()
-            .top_type()
-            .unwrap_or(TypeHierarchy::Other)
-            >= level
-        {
+        let top_type = match self.conventional.as_ref() {
+            Some(conventional) => conventional.top_type(),
+            None => return Err(Error::NoConventionalCommits),
+        };
+        log::debug!("top type found is {:?}", &top_type);
+
+        if let Some(top_type) = top_type.filter(|top_type| *top_type >= level) {
             let files = self.files.clone();
             if let Some(files) = files {
                 let mut missing_files = vec![];
@@ -374,7 +2606,7 @@ impl VersionCalculator {
                 }
 
                 if !missing_files.is_empty() {
-                    return Err(Error::MissingRequiredFile(missing_files));
+                    return Err(Error::MissingRequiredFile(missing_files, level, top_type));
                 }
             } else {
                 return Err(Error::NoFilesListed);
@@ -383,6 +2615,55 @@ impl VersionCalculator {
 
         Ok(())
     }
+
+    /// Build the plan for the next release
+    ///
+    /// The commits are walked if they have not been walked already. The
+    /// plan reports the next version (a pre-release if a label is given
+    /// in the options), the changelog, the result of the required files
+    /// check and the counts of the commits.
+    ///
+    /// ## Error
+    ///
+    /// Report error if the commits cannot be walked or the next version
+    /// cannot be calculated.
+    ///
+    pub fn plan(mut self, options: PlanOptions) -> Result<ReleasePlan, Error> {
+        if self.conventional.is_none() {
+            self = self.walk_commits()?;
+        }
+        let answer = match &options.pre_release {
+            Some(label) => self.next_pre_release(label)?,
+            None => self.next_version(),
+        };
+        let required_ok = if options.required.is_empty() {
+            true
+        } else {
+            match self.has_required(options.required, options.enforce_level) {
+                Ok(()) => true,
+                Err(Error::MissingRequiredFile(..)) => false,
+                Err(e) => return Err(e),
+            }
+        };
+        let metrics = match &self.conventional {
+            Some(conventional) => PlanMetrics {
+                commits: conventional.commits_count(),
+                breaking: conventional.breaking_count(),
+                types: conventional.counts().into_iter().collect(),
+                authors: self.authors.clone(),
+            },
+            None => PlanMetrics::default(),
+        };
+        Ok(ReleasePlan {
+            previous: self.current_version.clone(),
+            changelog: Changelog::new(&answer.version_number, self.commits()),
+            tag_name: self.tag_name(&answer.version_number),
+            next: answer.version_number,
+            level: answer.bump_level,
+            required_ok,
+            metrics,
+        })
+    }
 }
 
 fn next_version_calculator(mut version: Semantic, bump: &Level) -> Semantic {
@@ -393,3 +2674,501 @@ fn next_version_calculator(mut version: Semantic, bump: &Level) -> Semantic {
         _ => version,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn civil_date_converts_days_since_the_epoch() {
+        let cases = [
+            (0, "1970-01-01"),
+            (-1, "1969-12-31"),
+            (11_016, "2000-02-29"),
+            (19_378, "2023-01-21"),
+        ];
+        for (days, date) in cases {
+            assert_eq!(date, civil_date(days));
+        }
+    }
+
+    #[test]
+    fn force_options_convert_to_force_level() {
+        let cases = [
+            (ForceOptions::Major, ForceLevel::Major),
+            (ForceOptions::Minor, ForceLevel::Minor),
+            (ForceOptions::Patch, ForceLevel::Patch),
+        ];
+        for (option, level) in cases {
+            assert_eq!(level, ForceLevel::try_from(option).unwrap());
+        }
+        assert!(matches!(
+            ForceLevel::try_from(ForceOptions::First),
+            Err(Error::NotForceLevel(_))
+        ));
+    }
+
+    #[test]
+    fn level_converts_to_force_level() {
+        assert_eq!(
+            ForceLevel::Minor,
+            ForceLevel::try_from(Level::Minor).unwrap()
+        );
+        assert!(matches!(
+            ForceLevel::try_from(Level::None),
+            Err(Error::NotForceLevel(_))
+        ));
+    }
+
+    fn calculator(version: &str, conventional: Option<ConventionalCommits>) -> VersionCalculator {
+        let mut calculator = VersionCalculator::with_baseline(
+            PathBuf::from("."),
+            Semantic::parse(version, "v").unwrap(),
+            Baseline::Latest,
+            None,
+        );
+        calculator.conventional = conventional;
+        calculator
+    }
+
+    #[test]
+    fn previews_bump_each_level() {
+        let previews = calculator("v1.2.3", None).previews();
+
+        assert_eq!("v1.2.4", previews.patch.to_string());
+        assert_eq!("v1.3.0", previews.minor.to_string());
+        assert_eq!("v2.0.0", previews.major.to_string());
+        assert_eq!(
+            "patch: v1.2.4, minor: v1.3.0, major: v2.0.0",
+            previews.to_string()
+        );
+    }
+
+    #[test]
+    fn previews_label_the_first_production_release_before_1_0_0() {
+        let previews = calculator("v0.3.1", None).previews();
+
+        assert!(previews.initial_development);
+        assert_eq!(
+            "patch: v0.3.2, minor: v0.4.0, first production: v1.0.0",
+            previews.to_string()
+        );
+        assert!(
+            !calculator("v0.3.1", None)
+                .semver_strict()
+                .previews()
+                .initial_development
+        );
+    }
+
+    #[test]
+    fn compute_from_immutable_reference() {
+        let mut conventional = ConventionalCommits::new();
+        conventional.push_type("feat", false);
+        let calculator = calculator("v1.2.3", Some(conventional));
+        let calculator_ref = &calculator;
+
+        let answer = calculator_ref.compute();
+
+        assert_eq!(Level::Minor, answer.bump_level);
+        assert_eq!("v1.3.0", answer.version_number.to_string());
+    }
+
+    #[test]
+    fn bump_level_for_each_change() {
+        let breaking = {
+            let mut conventional = ConventionalCommits::new();
+            conventional.push_forced(&ForceLevel::Major);
+            conventional
+        };
+        let feat = {
+            let mut conventional = ConventionalCommits::new();
+            conventional.push_type("feat", false);
+            conventional
+        };
+        let chore = {
+            let mut conventional = ConventionalCommits::new();
+            conventional.push_type("chore", false);
+            conventional
+        };
+        let cases = [
+            ("v1.2.3", Some(breaking.clone()), Level::Major),
+            ("v1.2.3", Some(feat.clone()), Level::Minor),
+            ("v1.2.3", Some(chore.clone()), Level::Patch),
+            ("v1.2.3", Some(ConventionalCommits::new()), Level::None),
+            ("v1.2.3", None, Level::None),
+            ("v0.2.3", Some(breaking), Level::Minor),
+            ("v0.2.3", Some(feat), Level::Patch),
+            ("v0.2.3", Some(chore), Level::Patch),
+        ];
+        for (version, conventional, expected) in cases {
+            let calculator = calculator(version, conventional);
+
+            assert_eq!(expected, calculator.bump_level(), "{}", version);
+            assert_eq!(expected, calculator.compute().bump_level, "{}", version);
+        }
+    }
+
+    #[test]
+    fn promote_first_from_zero_major() {
+        let mut calculator = calculator("v0.7.9", Some(ConventionalCommits::new()));
+
+        let answer = calculator.promote_first().unwrap();
+
+        assert_eq!(Level::Major, answer.bump_level);
+        assert_eq!("v1.0.0", answer.version_number.to_string());
+    }
+
+    #[test]
+    fn promote_first_error_when_major_used() {
+        let mut calculator = calculator("v1.0.0", Some(ConventionalCommits::new()));
+        let unchanged = calculator.clone();
+
+        assert_eq!(
+            "First production release already deployed. Current major version: 1",
+            calculator.promote_first().unwrap_err().to_string()
+        );
+        assert_eq!(unchanged, calculator);
+    }
+
+    #[test]
+    fn promote_first_to_default_version() {
+        let mut calculator = calculator("v0.7.9", Some(ConventionalCommits::new()));
+
+        let answer = calculator.promote_first_to("1.0.0").unwrap();
+
+        assert_eq!(Level::Major, answer.bump_level);
+        assert_eq!("v1.0.0", answer.version_number.to_string());
+    }
+
+    #[test]
+    fn promote_first_to_supplied_version() {
+        let mut calculator = calculator("v0.7.9", Some(ConventionalCommits::new()));
+
+        let answer = calculator.promote_first_to("v3.0.0").unwrap();
+
+        assert_eq!(Level::Major, answer.bump_level);
+        assert_eq!("v3.0.0", answer.version_number.to_string());
+    }
+
+    #[test]
+    fn promote_first_to_rejects_version_below_current() {
+        let mut calculator = calculator("v3.1.0", Some(ConventionalCommits::new()));
+
+        assert_eq!(
+            "Version v3.0.0 is not greater than the current version v3.1.0",
+            calculator
+                .promote_first_to("3.0.0")
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn promote_first_to_rejects_version_below_one() {
+        let mut calculator = calculator("v0.7.9", Some(ConventionalCommits::new()));
+
+        assert!(matches!(
+            calculator.promote_first_to("0.8.0"),
+            Err(Error::FirstProductionTooLow(_))
+        ));
+    }
+
+    #[test]
+    fn promote_if_breaking_with_breaking_change() {
+        let mut conventional = ConventionalCommits::new();
+        conventional.push_forced(&ForceLevel::Major);
+        let calculator = calculator("v0.7.9", Some(conventional));
+
+        let answer = calculator.promote_if_breaking().unwrap();
+
+        assert_eq!(Level::Major, answer.bump_level);
+        assert_eq!("v1.0.0", answer.version_number.to_string());
+    }
+
+    #[test]
+    fn promote_if_breaking_without_breaking_change() {
+        let mut conventional = ConventionalCommits::new();
+        conventional.push_type("feat", false);
+        let calculator = calculator("v0.7.9", Some(conventional));
+
+        let answer = calculator.promote_if_breaking().unwrap();
+
+        assert_eq!(Level::Patch, answer.bump_level);
+        assert_eq!("v0.7.10", answer.version_number.to_string());
+    }
+
+    #[test]
+    fn next_version_is_never_lower_than_current_version() {
+        // pre-release versions are checked in tests/calculator_tests.rs
+        let versions = ["v0.0.0", "v0.7.9", "v1.2.3"];
+        for version in versions {
+            let current = Semantic::parse(version, "v").unwrap();
+
+            // no change
+            let answer = calculator(version, None).compute();
+            assert_eq!(current, answer.version_number);
+            assert_eq!(Level::None, answer.bump_level);
+
+            // release
+            for (type_, breaking) in [("fix", false), ("feat", false), ("feat", true)] {
+                let mut conventional = ConventionalCommits::new();
+                conventional.push_type(type_, breaking);
+                let answer = calculator(version, Some(conventional)).compute();
+                assert!(answer.version_number > current, "{} {}", version, type_);
+            }
+
+            // forced changes
+            for level in [ForceLevel::Major, ForceLevel::Minor, ForceLevel::Patch] {
+                let answer = calculator(version, None).force(level).compute();
+                assert!(answer.version_number > current, "{}", version);
+            }
+
+            // first production release
+            if current.major() == 0 {
+                let answer = calculator(version, None).promote_first().unwrap();
+                assert!(answer.version_number > current, "{}", version);
+            }
+        }
+    }
+
+    #[test]
+    fn compute_releases_the_core_from_the_release_found_by_the_walk() {
+        let cases = [
+            ("fix", false, Level::Patch, "v1.3.0"),
+            ("feat", false, Level::Minor, "v1.3.0"),
+            ("fix", true, Level::Major, "v2.0.0"),
+        ];
+        for (type_, breaking, since_release, expected) in cases {
+            let mut conventional = ConventionalCommits::new();
+            conventional.push_type(type_, breaking);
+            let mut calculator = calculator("v1.3.0-rc.1", Some(conventional));
+            // the repository is not opened by compute
+            calculator.repo_path = PathBuf::from("/nonexistent");
+            calculator.since_release =
+                Some((Semantic::parse("v1.2.0", "v").unwrap(), since_release));
+
+            let answer = calculator.compute();
+
+            assert_eq!(expected, answer.version_number.to_string(), "{}", type_);
+        }
+    }
+
+    fn versions(tags: &[&str]) -> Vec<Semantic> {
+        tags.iter()
+            .map(|tag| Semantic::parse(tag, "v").unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn pre_release_version_continues_each_stage() {
+        let existing = versions(&[
+            "v1.1.0",
+            "v1.2.0-alpha.1",
+            "v1.2.0-alpha.3",
+            "v1.2.0-beta.1",
+            "v1.3.0-beta.7",
+        ]);
+        let core = Semantic::parse("v1.2.0", "v").unwrap();
+
+        let cases = [
+            ("alpha", "v1.2.0-alpha.4"),
+            ("beta", "v1.2.0-beta.2"),
+            ("rc", "v1.2.0-rc.1"),
+        ];
+        for (label, expected) in cases {
+            let version = pre_release_version(core.clone(), label, &existing).unwrap();
+
+            assert_eq!(expected, version.to_string(), "{}", label);
+        }
+    }
+
+    #[test]
+    fn pre_release_version_starts_series_for_new_core() {
+        let existing = versions(&["v1.2.0-beta.1"]);
+        let core = Semantic::parse("v1.3.0", "v").unwrap();
+
+        let version = pre_release_version(core, "beta", &existing).unwrap();
+
+        assert_eq!("v1.3.0-beta.1", version.to_string());
+    }
+
+    #[test]
+    fn next_pre_release_without_change_returns_current_version() {
+        let mut calculator = calculator("v1.2.0", None);
+
+        let answer = calculator.next_pre_release("rc").unwrap();
+
+        assert_eq!(Level::None, answer.bump_level);
+        assert_eq!("v1.2.0", answer.version_number.to_string());
+    }
+
+    #[test]
+    fn forced_patch_colliding_with_existing_tag_is_a_regression() {
+        let answer = calculator("v1.2.3", None)
+            .force(ForceLevel::Patch)
+            .compute();
+        let existing = ["v1.2.3", "v1.2.4", "v1.2.4-rc.1"]
+            .iter()
+            .map(|tag| Semantic::parse(tag, "v").unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!("v1.2.4", answer.version_number.to_string());
+        assert_eq!(
+            Some("v1.2.4".to_string()),
+            regression(&answer.version_number, &existing).map(|v| v.to_string())
+        );
+    }
+
+    #[test]
+    fn forced_patch_below_existing_higher_tag_is_a_regression() {
+        let answer = calculator("v1.2.3", None)
+            .force(ForceLevel::Patch)
+            .compute();
+        let existing = ["v1.2.3", "v1.3.0"]
+            .iter()
+            .map(|tag| Semantic::parse(tag, "v").unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            Some("v1.3.0".to_string()),
+            regression(&answer.version_number, &existing).map(|v| v.to_string())
+        );
+    }
+
+    #[test]
+    fn next_version_above_existing_tags_is_not_a_regression() {
+        let answer = calculator("v1.2.3", None)
+            .force(ForceLevel::Patch)
+            .compute();
+        let existing = ["v1.2.2", "v1.2.3", "v1.2.4-rc.1"]
+            .iter()
+            .map(|tag| Semantic::parse(tag, "v").unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(None, regression(&answer.version_number, &existing));
+    }
+
+    fn zero_major(breaking: bool) -> ConventionalCommits {
+        let mut conventional = ConventionalCommits::new();
+        conventional.push_type("feat", breaking);
+        conventional
+    }
+
+    #[test]
+    fn zero_major_feature_bumps_patch() {
+        let answer = calculator("v0.3.0", Some(zero_major(false))).compute();
+        assert_eq!("v0.3.1", answer.version_number.to_string());
+
+        let answer = calculator("v0.3.0", Some(zero_major(false)))
+            .zero_feat_as_patch()
+            .compute();
+        assert_eq!("v0.3.1", answer.version_number.to_string());
+    }
+
+    #[test]
+    fn zero_feat_as_patch_takes_precedence_over_breaking() {
+        let answer = calculator("v0.3.0", Some(zero_major(true))).compute();
+        assert_eq!(Level::Minor, answer.bump_level);
+        assert_eq!("v0.4.0", answer.version_number.to_string());
+
+        let answer = calculator("v0.3.0", Some(zero_major(true)))
+            .zero_feat_as_patch()
+            .compute();
+        assert_eq!(Level::Patch, answer.bump_level);
+        assert_eq!("v0.3.1", answer.version_number.to_string());
+    }
+
+    #[test]
+    fn semver_strict_applies_the_stable_rules_before_1_0_0() {
+        let answer = calculator("v0.3.0", Some(zero_major(true)))
+            .semver_strict()
+            .compute();
+        assert_eq!(Level::Major, answer.bump_level);
+        assert_eq!("v1.0.0", answer.version_number.to_string());
+
+        let answer = calculator("v0.3.0", Some(zero_major(false)))
+            .semver_strict()
+            .compute();
+        assert_eq!(Level::Minor, answer.bump_level);
+        assert_eq!("v0.4.0", answer.version_number.to_string());
+    }
+
+    #[test]
+    fn semver_strict_takes_precedence_over_zero_feat_as_patch() {
+        let answer = calculator("v0.3.0", Some(zero_major(true)))
+            .zero_feat_as_patch()
+            .semver_strict()
+            .compute();
+        assert_eq!("v1.0.0", answer.version_number.to_string());
+    }
+
+    #[test]
+    fn zero_feat_as_patch_ignored_from_first_production() {
+        let answer = calculator("v1.3.0", Some(zero_major(false)))
+            .zero_feat_as_patch()
+            .compute();
+        assert_eq!("v1.4.0", answer.version_number.to_string());
+
+        let answer = calculator("v1.3.0", Some(zero_major(true)))
+            .zero_feat_as_patch()
+            .compute();
+        assert_eq!("v2.0.0", answer.version_number.to_string());
+    }
+
+    fn features(count: u32) -> ConventionalCommits {
+        let mut conventional = ConventionalCommits::new();
+        for _ in 0..count {
+            conventional.push_type("feat", false);
+        }
+        conventional
+    }
+
+    #[test]
+    fn fewer_features_than_the_minimum_bump_patch() {
+        let answer = calculator("v1.3.0", Some(features(1)))
+            .min_feats_for_minor(2)
+            .compute();
+        assert_eq!(Level::Patch, answer.bump_level);
+        assert_eq!("v1.3.1", answer.version_number.to_string());
+
+        let answer = calculator("v1.3.0", Some(features(2)))
+            .min_feats_for_minor(2)
+            .compute();
+        assert_eq!(Level::Minor, answer.bump_level);
+        assert_eq!("v1.4.0", answer.version_number.to_string());
+    }
+
+    #[test]
+    fn minimum_features_of_zero_or_one_is_the_default() {
+        for minimum in [0, 1] {
+            let answer = calculator("v1.3.0", Some(features(1)))
+                .min_feats_for_minor(minimum)
+                .compute();
+            assert_eq!(Level::Minor, answer.bump_level, "{}", minimum);
+        }
+        let answer = calculator("v1.3.0", Some(features(0)))
+            .min_feats_for_minor(0)
+            .compute();
+        assert_eq!(Level::None, answer.bump_level);
+    }
+
+    #[test]
+    fn compute_reports_baseline_tag() {
+        let calculator = calculator("v1.2.3", Some(ConventionalCommits::new()));
+
+        let answer = calculator.compute();
+
+        assert_eq!("v1.2.3", answer.baseline_tag);
+    }
+
+    #[test]
+    fn compute_without_conventional_commits_returns_current_version() {
+        let calculator = calculator("v1.2.3", None);
+
+        let answer = calculator.compute();
+
+        assert_eq!(Level::None, answer.bump_level);
+        assert_eq!("v1.2.3", answer.version_number.to_string());
+    }
+}
@@ -0,0 +1,422 @@
+//! A changelog for the next version built from the commits walked
+//!
+//! Breaking changes are listed first, followed by a section for each of
+//! the conventional types reported to users. Commits of other types are
+//! left out unless they are breaking.
+//!
+//! ```markdown
+//! ## [v1.3.0] - 2023-01-21
+//!
+//! ### Features
+//!
+//! - **cli:** add a flag (#123)
+//! ```
+//!
+//! Within a section the entries are listed newest first unless another
+//! `ChangelogSort` is chosen.
+//!
+//! Issues and pull requests referenced in the footers of a commit
+//! (e.g. `Closes #123`) are listed after the description.
+//!
+//! With a compare URL the changelog ends with a link for the version
+//! heading (e.g. `[v1.3.0]: https://github.com/owner/repo/compare/v1.2.3...v1.3.0`).
+//!
+//! The changelog can also be written as JSON for rendering with other
+//! templates:
+//!
+//! ```json
+//! {
+//!   "version": "v1.3.0",
+//!   "date": "2023-01-21",
+//!   "sections": [
+//!     {
+//!       "title": "Features",
+//!       "entries": [
+//!         { "scope": "cli", "description": "add a flag", "breaking": false, "refs": ["#123"] }
+//!       ]
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+
+use std::{cmp::Reverse, fmt};
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::{Error, ParsedCommit, Semantic};
+
+/// The sections for conventional types in the order they are listed
+///
+const SECTIONS: [(&str, &str); 4] = [
+    ("feat", "Features"),
+    ("fix", "Bug Fixes"),
+    ("perf", "Performance"),
+    ("revert", "Reverts"),
+];
+
+/// The title of the section for breaking changes
+///
+const BREAKING_SECTION: &str = "Breaking Changes";
+
+/// The order of the entries within a section of the changelog
+///
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangelogSort {
+    /// newest commit first
+    Newest,
+    /// oldest commit first
+    Date,
+    /// alphabetically by scope then description, unscoped entries last
+    Scope,
+}
+
+impl Default for ChangelogSort {
+    fn default() -> Self {
+        ChangelogSort::Newest
+    }
+}
+
+/// An entry in a section of the changelog
+///
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Entry {
+    /// the scope of the commit
+    pub scope: Option<String>,
+    /// the description of the commit
+    pub description: String,
+    /// the commit is a breaking change
+    pub breaking: bool,
+    /// the issues and pull requests referenced e.g. "#123"
+    #[serde(rename = "refs")]
+    pub references: Vec<String>,
+}
+
+impl From<&ParsedCommit> for Entry {
+    fn from(commit: &ParsedCommit) -> Self {
+        Entry {
+            scope: commit.scope.clone(),
+            description: commit.description.clone(),
+            breaking: commit.breaking,
+            references: commit.references.clone(),
+        }
+    }
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.scope {
+            Some(scope) => write!(f, "- **{}:** {}", scope, self.description)?,
+            None => write!(f, "- {}", self.description)?,
+        }
+        if !self.references.is_empty() {
+            write!(f, " ({})", self.references.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// A section of the changelog e.g. "Features"
+///
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Section {
+    /// the title of the section
+    pub title: String,
+    /// the entries in the section
+    pub entries: Vec<Entry>,
+}
+
+/// The changelog for a version
+///
+/// Displays as markdown.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Changelog {
+    /// the version the changelog is for
+    pub version: String,
+    /// the date of the release e.g. "2023-01-21"
+    pub date: Option<String>,
+    /// the URL comparing the previous version with the version, left
+    /// out of the JSON if not set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compare_url: Option<String>,
+    /// the sections that have entries
+    pub sections: Vec<Section>,
+}
+
+impl Changelog {
+    /// Build the changelog for the version from the commits
+    ///
+    /// The entries are listed newest first.
+    ///
+    pub fn new(version: &Semantic, commits: &[ParsedCommit]) -> Changelog {
+        Changelog::new_sorted(version, commits, ChangelogSort::Newest)
+    }
+
+    /// Build the changelog for the version from the commits with the
+    /// entries in each section in the order given
+    ///
+    /// Commits made at the same time are kept in the order walked.
+    ///
+    pub fn new_sorted(
+        version: &Semantic,
+        commits: &[ParsedCommit],
+        sort: ChangelogSort,
+    ) -> Changelog {
+        let mut commits: Vec<&ParsedCommit> = commits.iter().collect();
+        match sort {
+            ChangelogSort::Newest => commits.sort_by_key(|commit| Reverse(commit.time)),
+            ChangelogSort::Date => {
+                // commits are walked newest first
+                commits.reverse();
+                commits.sort_by_key(|commit| commit.time);
+            }
+            ChangelogSort::Scope => commits.sort_by(|a, b| {
+                (a.scope.is_none(), &a.scope, &a.description).cmp(&(
+                    b.scope.is_none(),
+                    &b.scope,
+                    &b.description,
+                ))
+            }),
+        }
+
+        let mut sections = vec![];
+        let breaking: Vec<Entry> = commits
+            .iter()
+            .filter(|commit| commit.breaking)
+            .map(|commit| Entry::from(*commit))
+            .collect();
+        if !breaking.is_empty() {
+            sections.push(Section {
+                title: BREAKING_SECTION.to_string(),
+                entries: breaking,
+            });
+        }
+        for (type_, title) in SECTIONS {
+            let entries: Vec<Entry> = commits
+                .iter()
+                .filter(|commit| !commit.breaking && commit.type_.as_deref() == Some(type_))
+                .map(|commit| Entry::from(*commit))
+                .collect();
+            if !entries.is_empty() {
+                sections.push(Section {
+                    title: title.to_string(),
+                    entries,
+                });
+            }
+        }
+        Changelog {
+            version: version.to_string(),
+            date: None,
+            compare_url: None,
+            sections,
+        }
+    }
+
+    /// Set the date of the release
+    ///
+    pub fn with_date(mut self, date: &str) -> Changelog {
+        self.date = Some(date.to_string());
+        self
+    }
+
+    /// Set the URL comparing the previous version with the version
+    ///
+    pub fn with_compare_url(mut self, url: &str) -> Changelog {
+        self.compare_url = Some(url.to_string());
+        self
+    }
+
+    /// Write the changelog as JSON
+    ///
+    /// ## Error
+    ///
+    /// Errors from serde_json are returned.
+    ///
+    pub fn to_json(&self) -> Result<String, Error> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Report if the changelog has no entries
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.sections.is_empty()
+    }
+}
+
+impl fmt::Display for Changelog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.date {
+            Some(date) => writeln!(f, "## [{}] - {}", self.version, date)?,
+            None => writeln!(f, "## [{}]", self.version)?,
+        }
+        for section in &self.sections {
+            writeln!(f)?;
+            writeln!(f, "### {}", section.title)?;
+            writeln!(f)?;
+            for entry in &section.entries {
+                writeln!(f, "{}", entry)?;
+            }
+        }
+        if let Some(url) = &self.compare_url {
+            writeln!(f)?;
+            writeln!(f, "[{}]: {}", self.version, url)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(type_: &str, scope: Option<&str>, description: &str, breaking: bool) -> ParsedCommit {
+        ParsedCommit {
+            oid: None,
+            type_: Some(type_.to_string()),
+            scope: scope.map(str::to_string),
+            description: description.to_string(),
+            breaking,
+            references: vec![],
+            time: 0,
+        }
+    }
+
+    fn descriptions(changelog: &Changelog) -> Vec<&str> {
+        changelog.sections[0]
+            .entries
+            .iter()
+            .map(|entry| entry.description.as_str())
+            .collect()
+    }
+
+    fn feats() -> Vec<ParsedCommit> {
+        // in the order walked, newest first
+        let mut commits = vec![
+            commit("feat", Some("cli"), "add a flag", false),
+            commit("feat", None, "add logging", false),
+            commit("feat", Some("api"), "add a method", false),
+            commit("feat", Some("cli"), "add a command", false),
+        ];
+        for (commit, time) in commits.iter_mut().zip([400, 300, 300, 100]) {
+            commit.time = time;
+        }
+        commits
+    }
+
+    #[test]
+    fn changelog_entries_newest_first_by_default() {
+        let version = Semantic::parse("v1.3.0", "v").unwrap();
+        let commits = feats();
+
+        let changelog = Changelog::new(&version, &commits);
+
+        assert_eq!(
+            vec!["add a flag", "add logging", "add a method", "add a command"],
+            descriptions(&changelog)
+        );
+    }
+
+    #[test]
+    fn changelog_entries_sorted_by_date() {
+        let version = Semantic::parse("v1.3.0", "v").unwrap();
+        let commits = feats();
+
+        let changelog = Changelog::new_sorted(&version, &commits, ChangelogSort::Date);
+
+        assert_eq!(
+            vec!["add a command", "add a method", "add logging", "add a flag"],
+            descriptions(&changelog)
+        );
+    }
+
+    #[test]
+    fn changelog_entries_sorted_by_scope() {
+        let version = Semantic::parse("v1.3.0", "v").unwrap();
+        let commits = feats();
+
+        let changelog = Changelog::new_sorted(&version, &commits, ChangelogSort::Scope);
+
+        assert_eq!(
+            vec!["add a method", "add a command", "add a flag", "add logging"],
+            descriptions(&changelog)
+        );
+    }
+
+    #[test]
+    fn changelog_groups_commits_by_section() {
+        let version = Semantic::parse("v2.0.0", "v").unwrap();
+        let commits = [
+            commit("fix", None, "correct a bug", false),
+            commit("chore", None, "tidy the build", false),
+            commit("feat", Some("cli"), "add a flag", false),
+            commit("refactor", None, "replace the interface", true),
+        ];
+
+        let changelog = Changelog::new(&version, &commits);
+
+        let expected = "\
+## [v2.0.0]
+
+### Breaking Changes
+
+- replace the interface
+
+### Features
+
+- **cli:** add a flag
+
+### Bug Fixes
+
+- correct a bug
+";
+        assert_eq!(expected, changelog.to_string());
+    }
+
+    #[test]
+    fn changelog_as_json_lists_the_sections() {
+        let version = Semantic::parse("v1.3.0", "v").unwrap();
+        let mut fix = commit("fix", None, "correct a bug", false);
+        fix.references = vec!["#12".to_string()];
+        let commits = [commit("feat", Some("cli"), "add a flag", false), fix];
+
+        let changelog = Changelog::new(&version, &commits).with_date("2023-01-21");
+
+        assert_eq!(
+            "## [v1.3.0] - 2023-01-21",
+            changelog.to_string().lines().next().unwrap()
+        );
+        let json: serde_json::Value = serde_json::from_str(&changelog.to_json().unwrap()).unwrap();
+        let expected = serde_json::json!({
+            "version": "v1.3.0",
+            "date": "2023-01-21",
+            "sections": [
+                {
+                    "title": "Features",
+                    "entries": [
+                        {"scope": "cli", "description": "add a flag", "breaking": false, "refs": []}
+                    ]
+                },
+                {
+                    "title": "Bug Fixes",
+                    "entries": [
+                        {"scope": null, "description": "correct a bug", "breaking": false, "refs": ["#12"]}
+                    ]
+                }
+            ]
+        });
+        assert_eq!(expected, json);
+    }
+
+    #[test]
+    fn changelog_without_reported_commits_is_empty() {
+        let version = Semantic::parse("v1.2.4", "v").unwrap();
+        let commits = [commit("docs", None, "update the readme", false)];
+
+        let changelog = Changelog::new(&version, &commits);
+
+        assert!(changelog.is_empty());
+        assert_eq!("## [v1.2.4]\n", changelog.to_string());
+    }
+}
@@ -0,0 +1,129 @@
+//! Conventional commit types read from a commitlint config
+//!
+//! Projects using [commitlint](https://commitlint.js.org) list the
+//! commit types allowed in the `type-enum` rule of `.commitlintrc.json`:
+//!
+//! ```json
+//! {
+//!   "rules": {
+//!     "type-enum": [2, "always", ["feat", "fix", "docs", "chore"]]
+//!   }
+//! }
+//! ```
+//!
+//! Only commits of the types listed are counted. If the file or the
+//! rule is absent the built-in types are used.
+//!
+
+use std::{fs, io, path::Path};
+
+use serde_json::Value;
+
+use crate::Error;
+
+/// The default name of the commitlint config file
+pub const COMMITLINT_CONFIG: &str = ".commitlintrc.json";
+
+/// The conventional commit settings read from a commitlint config
+///
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CommitlintConfig {
+    types: Option<Vec<String>>,
+}
+
+impl CommitlintConfig {
+    /// Read the config from the file at path
+    ///
+    /// A missing file gives the default config using the built-in types.
+    ///
+    /// ## Error
+    ///
+    /// Report error if the file cannot be read or the `type-enum` rule
+    /// is not valid.
+    ///
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<CommitlintConfig, Error> {
+        let path = path.as_ref();
+        match fs::read_to_string(path) {
+            Ok(content) => CommitlintConfig::parse(&content),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                log::debug!("no commitlint config at {}", path.display());
+                Ok(CommitlintConfig::default())
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Parse the config from the JSON content
+    ///
+    /// ## Error
+    ///
+    /// Report error if the content is not JSON or the `type-enum` rule
+    /// is not a `[level, applicable, [types]]` array of strings.
+    ///
+    pub fn parse(content: &str) -> Result<CommitlintConfig, Error> {
+        let config: Value = serde_json::from_str(content)?;
+        let rule = match config.pointer("/rules/type-enum") {
+            Some(rule) => rule,
+            None => {
+                log::debug!("no type-enum rule in the commitlint config");
+                return Ok(CommitlintConfig::default());
+            }
+        };
+        let invalid = || Error::InvalidCommitlintConfig(rule.to_string());
+        let types = rule
+            .get(2)
+            .and_then(Value::as_array)
+            .ok_or_else(invalid)?
+            .iter()
+            .map(|type_| type_.as_str().map(str::to_string).ok_or_else(invalid))
+            .collect::<Result<Vec<String>, Error>>()?;
+        log::debug!("commitlint types: {:?}", &types);
+        Ok(CommitlintConfig { types: Some(types) })
+    }
+
+    /// Report the types allowed or None to use the built-in types
+    ///
+    pub fn types(&self) -> Option<&[String]> {
+        self.types.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_reads_the_type_enum_rule() {
+        let content = r#"{
+            "extends": ["@commitlint/config-conventional"],
+            "rules": {
+                "type-enum": [2, "always", ["feat", "fix", "deps"]]
+            }
+        }"#;
+
+        let config = CommitlintConfig::parse(content).unwrap();
+
+        assert_eq!(
+            Some(&["feat".to_string(), "fix".to_string(), "deps".to_string()][..]),
+            config.types()
+        );
+    }
+
+    #[test]
+    fn parse_without_type_enum_uses_built_in_types() {
+        let content = r#"{"rules": {"header-max-length": [2, "always", 72]}}"#;
+
+        let config = CommitlintConfig::parse(content).unwrap();
+
+        assert_eq!(None, config.types());
+    }
+
+    #[test]
+    fn parse_rejects_invalid_type_enum() {
+        let content = r#"{"rules": {"type-enum": [2, "always", "feat"]}}"#;
+
+        let result = CommitlintConfig::parse(content);
+
+        assert!(matches!(result, Err(Error::InvalidCommitlintConfig(_))));
+    }
+}
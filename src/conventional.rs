@@ -1,11 +1,11 @@
 //! Represents a vector of conventional commits
 //!
 
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt};
 
 use clap::ValueEnum;
 
-use crate::Error;
+use crate::{Error, ForceLevel};
 
 /// TypeHierarchy maps the types identified by git_conventional to a hierarchy of levels
 ///
@@ -22,6 +22,8 @@ use crate::Error;
 /// - PERF: Other
 /// - TEST: Other
 /// - CHORE: Other
+/// - BUILD: Other
+/// - CI: Other
 ///
 /// If a breaking change is found it sets breaking hierarchy.
 ///
@@ -51,17 +53,136 @@ impl TypeHierarchy {
             "perf" => TypeHierarchy::Other,
             "test" => TypeHierarchy::Other,
             "chore" => TypeHierarchy::Other,
+            "build" => TypeHierarchy::Other,
+            "ci" => TypeHierarchy::Other,
             "breaking" => TypeHierarchy::Breaking,
             _ => return Err(Error::NotTypeHierachyName(s.to_string())),
         })
     }
 }
+
+impl fmt::Display for TypeHierarchy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            TypeHierarchy::Other => "other",
+            TypeHierarchy::Fix => "fix",
+            TypeHierarchy::Feature => "feature",
+            TypeHierarchy::Breaking => "breaking",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// How dependency update commits are counted
+///
+/// A dependency update commit has the type `deps` or the scope `deps`
+/// (e.g. `chore(deps): bump serde`).
+///
+#[derive(Debug, PartialEq, Eq, Clone, Copy, ValueEnum)]
+pub enum DepsBump {
+    /// ignore dependency updates
+    None,
+    /// count dependency updates as a patch change (the default)
+    Patch,
+}
+
+impl Default for DepsBump {
+    fn default() -> Self {
+        DepsBump::Patch
+    }
+}
+
+/// How revert commits (type `revert`) are counted
+///
+#[derive(Debug, PartialEq, Eq, Clone, Copy, ValueEnum)]
+pub enum RevertBump {
+    /// count reverts as a fix (the default)
+    Fix,
+    /// ignore reverts as they do not change the release
+    None,
+}
+
+impl Default for RevertBump {
+    fn default() -> Self {
+        RevertBump::Fix
+    }
+}
+
+/// How conventional commits with a type that is not known (e.g.
+/// `security:`) are counted
+///
+/// Breaking changes are always counted whatever the type.
+///
+#[derive(Debug, PartialEq, Eq, Clone, Copy, ValueEnum)]
+pub enum UnknownType {
+    /// count unknown types as a patch change at the other level (the
+    /// default)
+    Patch,
+    /// ignore unknown types
+    Ignore,
+}
+
+impl Default for UnknownType {
+    fn default() -> Self {
+        UnknownType::Patch
+    }
+}
+
+/// The conventional commit types recognised
+///
+const KNOWN_TYPES: [&str; 11] = [
+    "feat", "fix", "docs", "style", "refactor", "perf", "test", "chore", "build", "ci", "revert",
+];
+
+/// List the conventional commit types recognised
+///
+/// ## Example
+///
+/// ```rust
+/// assert!(nextsv::known_types().contains(&"feat"));
+/// ```
+pub fn known_types() -> &'static [&'static str] {
+    &KNOWN_TYPES
+}
+
+/// A commit found during the walk
+///
+/// The conventional commit is parsed from the summary of the commit. If
+/// the summary is not a conventional commit the type is None and the
+/// description is the summary.
+///
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParsedCommit {
+    /// the id of the commit
+    pub oid: Option<git2::Oid>,
+    /// the type the commit is counted as e.g. "feat"
+    pub type_: Option<String>,
+    /// the scope of the commit
+    pub scope: Option<String>,
+    /// the description of the commit
+    pub description: String,
+    /// the commit is counted as a breaking change
+    pub breaking: bool,
+    /// the issues and pull requests referenced in the footers e.g. "#123"
+    pub references: Vec<String>,
+    /// the time of the commit in seconds since the epoch
+    pub time: i64,
+}
+
 #[derive(Default, Debug, PartialEq, Eq, Clone)]
 pub struct ConventionalCommits {
-    commits: Vec<String>,
-    counts: HashMap<String, u32>,
-    breaking: bool,
-    top_type: Option<TypeHierarchy>,
+    commits: Vec<ParsedCommit>,
+    breaking_scope: Option<String>,
+    ignore_types: Vec<String>,
+    collapse_types: Vec<String>,
+    allowed_types: Option<Vec<String>>,
+    breaking_footer_always: bool,
+    deps_bump: DepsBump,
+    revert_bump: RevertBump,
+    unknown_type: UnknownType,
+    expand_squash_body: bool,
+    conventional_count: usize,
+    type_levels: HashMap<String, TypeHierarchy>,
 }
 
 impl ConventionalCommits {
@@ -69,140 +190,542 @@ impl ConventionalCommits {
         ConventionalCommits::default()
     }
 
+    /// Only count breaking changes that have no scope or the scope given
+    ///
+    /// Breaking changes with a different scope are counted at the level
+    /// of their type as if they were not breaking.
+    ///
+    pub fn set_breaking_scope(&mut self, scope: Option<String>) -> &mut Self {
+        self.breaking_scope = scope;
+        self
+    }
+
+    /// Ignore commits of the types given
+    ///
+    pub fn set_ignore_types(&mut self, types: Vec<String>) -> &mut Self {
+        self.ignore_types = types;
+        self
+    }
+
+    /// Count commits of the types given at most once in the number of
+    /// commits of all types
+    ///
+    pub fn set_collapse_types(&mut self, types: Vec<String>) -> &mut Self {
+        self.collapse_types = types;
+        self
+    }
+
+    /// Only count commits of the types given
+    ///
+    /// If None commits of all types are counted.
+    ///
+    pub fn set_allowed_types(&mut self, types: Option<Vec<String>>) -> &mut Self {
+        self.allowed_types = types;
+        self
+    }
+
+    /// Scan the message of commits that are not conventional for a
+    /// `BREAKING CHANGE:` footer
+    ///
+    pub fn set_breaking_footer_always(&mut self, flag: bool) -> &mut Self {
+        self.breaking_footer_always = flag;
+        self
+    }
+
+    /// Count the conventional commits listed as bullet lines (`- feat: ...`)
+    /// in the body of a squash commit as separate commits
+    ///
+    pub fn set_expand_squash_body(&mut self, flag: bool) -> &mut Self {
+        self.expand_squash_body = flag;
+        self
+    }
+
+    /// Set how dependency update commits are counted
+    ///
+    pub fn set_deps_bump(&mut self, deps_bump: DepsBump) -> &mut Self {
+        self.deps_bump = deps_bump;
+        self
+    }
+
+    /// Set how revert commits are counted
+    ///
+    pub fn set_revert_bump(&mut self, revert_bump: RevertBump) -> &mut Self {
+        self.revert_bump = revert_bump;
+        self
+    }
+
+    /// Set how commits with an unknown type are counted
+    ///
+    pub fn set_unknown_type(&mut self, unknown_type: UnknownType) -> &mut Self {
+        self.unknown_type = unknown_type;
+        self
+    }
+
+    /// Set the level commits of each type are counted at, replacing the
+    /// level of the type in `TypeHierarchy::parse`
+    ///
+    pub fn set_type_levels(&mut self, type_levels: HashMap<String, TypeHierarchy>) -> &mut Self {
+        self.type_levels = type_levels;
+        self
+    }
+
+    /// The level commits of the type are counted at
+    ///
+    /// A type that is not known and not set is counted at the other level.
+    ///
+    pub fn type_level(&self, type_: &str) -> TypeHierarchy {
+        if let Some(level) = self.type_levels.get(type_) {
+            return level.clone();
+        }
+        TypeHierarchy::parse(type_).unwrap_or_else(|_| {
+            log::debug!("commit of unknown type {} counted as other", type_);
+            TypeHierarchy::Other
+        })
+    }
+
+    fn breaking_in_scope(&self, scope: Option<git_conventional::Scope>) -> bool {
+        match (&self.breaking_scope, scope) {
+            (Some(required), Some(scope)) => required.as_str() == scope.as_str(),
+            _ => true,
+        }
+    }
+
     pub fn push(&mut self, commit: &git2::Commit) -> &Self {
-        if commit.summary().take().unwrap_or("No") != "No" {
-            if let Ok(conventional) = git_conventional::Commit::parse(
-                commit.summary().take().unwrap_or("NotConventional"),
-            ) {
-                self.increment_counts(conventional.type_());
-
-                if !self.breaking {
-                    if conventional.breaking() {
-                        self.breaking = conventional.breaking();
-                        self.set_top_type_if_higher("breaking");
-                    } else {
-                        self.set_top_type_if_higher(conventional.type_().as_str());
+        if commit.summary().unwrap_or("No") != "No" {
+            let summary = commit.summary().unwrap_or("NotConventional");
+            let parsed = match git_conventional::Commit::parse(summary) {
+                Ok(conventional) => {
+                    self.conventional_count += 1;
+                    self.parse_conventional(commit, &conventional)
+                }
+                Err(_) => {
+                    let breaking = self.breaking_footer_always && has_breaking_footer(commit);
+                    if breaking {
+                        log::debug!("breaking change footer found in non-conventional commit");
                     }
+                    Some(ParsedCommit {
+                        oid: Some(commit.id()),
+                        type_: None,
+                        scope: None,
+                        description: summary.to_string(),
+                        breaking,
+                        references: vec![],
+                        time: commit.time().seconds(),
+                    })
                 }
+            };
+            if let Some(mut parsed) = parsed {
+                parsed.references = issue_references(commit);
+                self.record(parsed);
+            }
+            if self.expand_squash_body {
+                self.push_squash_body(commit);
+            }
+        }
+        self
+    }
+
+    /// Push the conventional commits listed as bullet lines in the body
+    /// of a squash commit
+    ///
+    fn push_squash_body(&mut self, commit: &git2::Commit) {
+        let body = commit.body().unwrap_or_default();
+        for line in body.lines() {
+            let line = line.trim_start();
+            let bullet = match line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+                Some(bullet) => bullet.trim(),
+                None => continue,
+            };
+            if let Ok(conventional) = git_conventional::Commit::parse(bullet) {
+                log::debug!("squashed commit found: {}", bullet);
+                if let Some(parsed) = self.parse_conventional(commit, &conventional) {
+                    self.record(parsed);
+                }
+            }
+        }
+    }
+
+    /// Parse a conventional commit applying the type filters and the
+    /// breaking scope
+    ///
+    /// Returns None if the commit is not counted.
+    ///
+    fn parse_conventional(
+        &self,
+        commit: &git2::Commit,
+        conventional: &git_conventional::Commit,
+    ) -> Option<ParsedCommit> {
+        if self
+            .ignore_types
+            .iter()
+            .any(|t| t.as_str() == conventional.type_().as_str())
+        {
+            log::debug!("commit of type {} ignored", conventional.type_());
+            return None;
+        }
+        if let Some(allowed) = &self.allowed_types {
+            if !allowed
+                .iter()
+                .any(|t| t.as_str() == conventional.type_().as_str())
+            {
+                log::debug!("commit of type {} not allowed", conventional.type_());
+                return None;
             }
-            self.commits.push(
-                commit
-                    .summary()
-                    .take()
-                    .unwrap_or("NotConventional")
-                    .to_string(),
+        }
+        if self.deps_bump == DepsBump::None && is_deps(conventional) {
+            log::debug!("dependency update commit ignored");
+            return None;
+        }
+        if self.revert_bump == RevertBump::None
+            && conventional.type_() == git_conventional::Type::REVERT
+        {
+            log::debug!("revert commit ignored");
+            return None;
+        }
+        let breaking = conventional.breaking() && self.breaking_in_scope(conventional.scope());
+        let known = KNOWN_TYPES.contains(&conventional.type_().as_str());
+        if !known && !breaking && self.unknown_type == UnknownType::Ignore {
+            log::debug!("commit of unknown type {} ignored", conventional.type_());
+            return None;
+        }
+        if conventional.breaking() && !breaking {
+            log::debug!(
+                "breaking change out of scope counted as {}",
+                conventional.type_()
             );
         }
+        Some(ParsedCommit {
+            oid: Some(commit.id()),
+            type_: Some(conventional.type_().to_string()),
+            scope: conventional.scope().map(|scope| scope.to_string()),
+            description: conventional.description().to_string(),
+            breaking,
+            references: vec![],
+            time: commit.time().seconds(),
+        })
+    }
+
+    /// Push a commit counting it at the level given instead of the
+    /// level of its conventional commit type
+    ///
+    pub fn push_at_level(&mut self, commit: &git2::Commit, level: &ForceLevel) -> &Self {
+        let summary = commit.summary().unwrap_or("NotConventional");
+        let (scope, description) = match git_conventional::Commit::parse(summary) {
+            Ok(conventional) => (
+                conventional.scope().map(|scope| scope.to_string()),
+                conventional.description().to_string(),
+            ),
+            Err(_) => (None, summary.to_string()),
+        };
+        // the type is the type the commit is counted as
+        let type_ = match level {
+            ForceLevel::Major => None,
+            ForceLevel::Minor => Some(git_conventional::Type::FEAT.to_string()),
+            ForceLevel::Patch => Some(git_conventional::Type::FIX.to_string()),
+        };
+        self.record(ParsedCommit {
+            oid: Some(commit.id()),
+            type_,
+            scope,
+            description,
+            breaking: *level == ForceLevel::Major,
+            references: issue_references(commit),
+            time: commit.time().seconds(),
+        });
         self
     }
 
-    pub fn increment_counts(&mut self, commit_type: git_conventional::Type) {
-        let counter = self.counts.entry(commit_type.to_string()).or_insert(0);
-        *counter += 1;
+    /// Record a change at the level forced without a commit
+    ///
+    /// The change is counted as a breaking change, a `feat` or a `fix`.
+    ///
+    pub fn push_forced(&mut self, level: &ForceLevel) -> &Self {
+        let type_ = match level {
+            ForceLevel::Major => None,
+            ForceLevel::Minor => Some(git_conventional::Type::FEAT.to_string()),
+            ForceLevel::Patch => Some(git_conventional::Type::FIX.to_string()),
+        };
+        self.record(ParsedCommit {
+            oid: None,
+            type_,
+            scope: None,
+            description: format!("forced {} change", level),
+            breaking: *level == ForceLevel::Major,
+            references: vec![],
+            time: 0,
+        });
+        self
     }
 
+    /// Record a parsed commit
+    ///
+    fn record(&mut self, parsed: ParsedCommit) {
+        self.commits.push(parsed);
+    }
+
+    /// Record a change of the type without a commit
+    ///
+    #[cfg(test)]
+    pub(crate) fn push_type(&mut self, type_: &str, breaking: bool) -> &mut Self {
+        self.record(ParsedCommit {
+            oid: None,
+            type_: Some(type_.to_string()),
+            scope: None,
+            description: format!("{} change", type_),
+            breaking,
+            references: vec![],
+            time: 0,
+        });
+        self
+    }
+
+    /// The number of commits collected of each type
+    ///
     pub fn counts(&self) -> HashMap<String, u32> {
-        self.counts.clone()
+        let mut counts = HashMap::new();
+        for type_ in self
+            .commits
+            .iter()
+            .filter_map(|commit| commit.type_.as_ref())
+        {
+            *counts.entry(type_.clone()).or_insert(0) += 1;
+        }
+        counts
     }
 
     pub fn commits_by_type(&self, commit_type: &str) -> u32 {
-        self.counts.get(commit_type).unwrap_or(&0_u32).to_owned()
+        self.commits
+            .iter()
+            .filter(|commit| commit.type_.as_deref() == Some(commit_type))
+            .count() as u32
     }
 
+    /// The number of conventional commits counted
+    ///
+    /// Each of the collapsed types counts at most once.
+    ///
     pub fn commits_all_types(&self) -> u32 {
-        self.counts.values().sum()
+        self.counts()
+            .iter()
+            .map(|(type_, count)| {
+                if self.collapse_types.contains(type_) {
+                    (*count).min(1)
+                } else {
+                    *count
+                }
+            })
+            .sum()
     }
 
     pub fn breaking(&self) -> bool {
-        self.breaking
+        self.commits.iter().any(|commit| commit.breaking)
     }
 
-    /// Set the breaking flag value
+    /// The number of commits of the types counted at the feature level
     ///
-    pub fn set_breaking(&mut self, flag: bool) -> &mut Self {
-        self.breaking = flag;
-        self
+    pub fn feature_count(&self) -> u32 {
+        self.counts()
+            .iter()
+            .filter(|(type_, _)| self.type_level(type_) == TypeHierarchy::Feature)
+            .map(|(_, count)| *count)
+            .sum()
     }
 
-    fn set_top_type_if_higher(&mut self, type_: &str) -> &mut Self {
-        let th = TypeHierarchy::parse(type_);
-        if let Ok(th) = th {
-            #[allow(clippy::redundant_clone)]
-            if th.clone() as u32 > self.top_type_discriminant() {
-                self.top_type = Some(th)
-            }
-        }
+    /// The number of commits parsed as conventional commits
+    ///
+    /// Includes the conventional commits that are ignored or not allowed,
+    /// but not the commits squashed into the body of another commit, so
+    /// the number is counted during the walk rather than from the commits
+    /// collected.
+    ///
+    pub fn conventional_count(&self) -> usize {
+        self.conventional_count
+    }
 
-        self
+    /// The number of commits collected
+    ///
+    pub fn commits_count(&self) -> usize {
+        self.commits.len()
     }
 
-    /// top_type_discriminant
+    /// The commits collected
     ///
-    /// Returns the discriminant of a TypeHierarchy if the Option is Some
-    /// and returns 0 if the Option is None.  
+    pub fn commits(&self) -> &[ParsedCommit] {
+        &self.commits
+    }
+
+    /// The number of breaking changes collected
     ///
-    fn top_type_discriminant(&self) -> u32 {
-        if self.top_type.is_none() {
-            0_u32
-        } else {
-            self.top_type().unwrap() as u32
-        }
+    pub fn breaking_count(&self) -> u32 {
+        self.commits.iter().filter(|commit| commit.breaking).count() as u32
     }
 
     /// top_type
     ///
-    /// Returns the top type.
+    /// Returns the highest level of the commits collected. A type that
+    /// is not known is counted at the other level.
     ///
     pub fn top_type(&self) -> Option<TypeHierarchy> {
-        self.top_type.clone()
+        self.commits
+            .iter()
+            .filter_map(|commit| {
+                if commit.breaking {
+                    Some(TypeHierarchy::Breaking)
+                } else {
+                    commit.type_.as_deref().map(|type_| self.type_level(type_))
+                }
+            })
+            .max()
+    }
+}
+
+/// Check for a dependency update commit by type or scope
+///
+fn is_deps(conventional: &git_conventional::Commit) -> bool {
+    conventional.type_().as_str() == "deps"
+        || conventional
+            .scope()
+            .map_or(false, |scope| scope.as_str() == "deps")
+}
+
+/// Check the commit message for a `BREAKING CHANGE:` footer
+///
+fn has_breaking_footer(commit: &git2::Commit) -> bool {
+    commit
+        .message()
+        .unwrap_or_default()
+        .lines()
+        .any(|line| line.starts_with("BREAKING CHANGE:") || line.starts_with("BREAKING-CHANGE:"))
+}
+
+/// The footer tokens that reference an issue or pull request
+///
+const REFERENCE_TOKENS: [&str; 9] = [
+    "close", "closes", "closed", "fix", "fixes", "fixed", "resolves", "resolved", "refs",
+];
+
+/// Collect the issue and pull request references (e.g. `Closes #123`)
+/// from the footers of the commit message
+///
+fn issue_references(commit: &git2::Commit) -> Vec<String> {
+    footer_references(commit.message().unwrap_or_default())
+}
+
+fn footer_references(message: &str) -> Vec<String> {
+    let mut references = vec![];
+    // the summary is not a footer
+    for line in message.lines().skip(1) {
+        let (token, value) = match line.split_once(": ").or_else(|| line.split_once(' ')) {
+            Some(footer) => footer,
+            None => continue,
+        };
+        if !REFERENCE_TOKENS.contains(&token.to_lowercase().as_str()) {
+            continue;
+        }
+        for reference in value.split(|c: char| c == ',' || c.is_whitespace()) {
+            let is_issue = reference.len() > 1
+                && reference.starts_with('#')
+                && reference[1..].chars().all(|c| c.is_ascii_digit());
+            if is_issue && !references.iter().any(|r| r == reference) {
+                references.push(reference.to_string());
+            }
+        }
     }
+    references
 }
 
 #[cfg(test)]
 mod tests {
-    use super::ConventionalCommits;
+    use super::{footer_references, known_types, ConventionalCommits, TypeHierarchy};
+    use crate::ForceLevel;
 
     #[test]
-    fn top_discrimant_returns_0_for_none() {
-        let value_under_test = ConventionalCommits::new();
-        let expected = 0_u32;
+    fn collapsed_types_count_once_in_all_types() {
+        let mut value_under_test = ConventionalCommits::new();
+        for _ in 0..20 {
+            value_under_test.push_type("chore", false);
+        }
+        value_under_test.push_type("docs", false);
+        value_under_test.push_type("docs", false);
+        value_under_test.push_type("fix", false);
+        value_under_test.push_type("fix", false);
+        assert_eq!(24, value_under_test.commits_all_types());
+
+        value_under_test.set_collapse_types(vec!["chore".to_string(), "docs".to_string()]);
 
-        assert_eq!(expected, value_under_test.top_type_discriminant());
+        assert_eq!(4, value_under_test.commits_all_types());
+        assert_eq!(20, value_under_test.commits_by_type("chore"));
     }
 
     #[test]
-    fn top_discrimant_returns_other() {
-        let mut value_under_test = ConventionalCommits::new();
-        value_under_test.top_type = Some(crate::TypeHierarchy::Other);
-        let expected = 1_u32;
+    fn footer_references_collects_issue_numbers() {
+        let message = "fix: correct a bug\n\nRefs #12\n\nCloses #123, #45\nReviewed-by: Z\nFixes: #7\nCloses #123";
 
-        assert_eq!(expected, value_under_test.top_type_discriminant());
+        assert_eq!(vec!["#12", "#123", "#45", "#7"], footer_references(message));
     }
 
     #[test]
-    fn top_discrimant_returns_fix() {
-        let mut value_under_test = ConventionalCommits::new();
-        value_under_test.top_type = Some(crate::TypeHierarchy::Fix);
-        let expected = 2_u32;
+    fn footer_references_ignores_the_summary_and_other_footers() {
+        let message = "fix: closes #1\n\nSee #2 for details\nCloses #abc";
 
-        assert_eq!(expected, value_under_test.top_type_discriminant());
+        assert!(footer_references(message).is_empty());
     }
 
     #[test]
-    fn top_discrimant_returns_feature() {
-        let mut value_under_test = ConventionalCommits::new();
-        value_under_test.top_type = Some(crate::TypeHierarchy::Feature);
-        let expected = 3_u32;
+    fn known_types_lists_conventional_types() {
+        assert_eq!(
+            &[
+                "feat", "fix", "docs", "style", "refactor", "perf", "test", "chore", "build", "ci",
+                "revert"
+            ],
+            known_types()
+        );
+    }
+
+    #[test]
+    fn top_type_is_none_without_commits() {
+        let value_under_test = ConventionalCommits::new();
+
+        assert_eq!(None, value_under_test.top_type());
+        assert!(!value_under_test.breaking());
+    }
 
-        assert_eq!(expected, value_under_test.top_type_discriminant());
+    #[test]
+    fn top_type_is_the_highest_level_of_the_commits() {
+        let cases: [(&[(&str, bool)], TypeHierarchy); 4] = [
+            (
+                &[("chore", false), ("security", false)],
+                TypeHierarchy::Other,
+            ),
+            (&[("docs", false), ("fix", false)], TypeHierarchy::Fix),
+            (&[("fix", false), ("feat", false)], TypeHierarchy::Feature),
+            (&[("feat", false), ("docs", true)], TypeHierarchy::Breaking),
+        ];
+        for (commits, expected) in cases {
+            let mut value_under_test = ConventionalCommits::new();
+            for (type_, breaking) in commits {
+                value_under_test.push_type(type_, *breaking);
+            }
+
+            assert_eq!(Some(expected), value_under_test.top_type());
+        }
     }
 
     #[test]
-    fn top_discrimant_returns_breaking() {
+    fn counts_are_derived_from_the_commits() {
         let mut value_under_test = ConventionalCommits::new();
-        value_under_test.top_type = Some(crate::TypeHierarchy::Breaking);
-        let expected = 4_u32;
+        value_under_test.push_type("feat", false);
+        value_under_test.push_type("feat", true);
+        value_under_test.push_forced(&ForceLevel::Major);
+        value_under_test.push_forced(&ForceLevel::Patch);
 
-        assert_eq!(expected, value_under_test.top_type_discriminant());
+        assert_eq!(4, value_under_test.commits_count());
+        assert_eq!(2, value_under_test.commits_by_type("feat"));
+        assert_eq!(1, value_under_test.commits_by_type("fix"));
+        assert_eq!(3, value_under_test.commits_all_types());
+        assert_eq!(2, value_under_test.breaking_count());
+        assert!(value_under_test.breaking());
+        assert_eq!(2, value_under_test.feature_count());
+        assert_eq!(Some(&2), value_under_test.counts().get("feat"));
     }
 }
@@ -5,12 +5,48 @@ use std::ffi::OsString;
 use proc_exit::{Code, Exit};
 use thiserror::Error;
 
+use crate::TypeHierarchy;
+
 const EXIT_UNEXPECTED_ERROR: i32 = 10;
 const EXIT_NOT_CALCULATED_CODE: i32 = 12;
 const EXIT_MISSING_REQUIRED_CODE: i32 = 13;
 const EXIT_NOT_REQUIRED_LEVEL: i32 = 14;
 const EXIT_NO_FILES_LISTED: i32 = 15;
 
+/// The exit codes reported by the program
+///
+/// The defaults can be changed to match the conventions expected
+/// by a CI system.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExitCodes {
+    /// exit code for an unexpected error
+    pub unexpected_error: i32,
+    /// exit code when the version could not be calculated (git errors)
+    pub not_calculated: i32,
+    /// exit code when required files are missing
+    pub missing_required: i32,
+    /// exit code when the minimum change level is not met
+    pub not_required_level: i32,
+    /// exit code when no files have been listed
+    pub no_files_listed: i32,
+    /// exit code when there is no change to release
+    pub no_change: i32,
+}
+
+impl Default for ExitCodes {
+    fn default() -> Self {
+        ExitCodes {
+            unexpected_error: EXIT_UNEXPECTED_ERROR,
+            not_calculated: EXIT_NOT_CALCULATED_CODE,
+            missing_required: EXIT_MISSING_REQUIRED_CODE,
+            not_required_level: EXIT_NOT_REQUIRED_LEVEL,
+            no_files_listed: EXIT_NO_FILES_LISTED,
+            no_change: 0,
+        }
+    }
+}
+
 /// The error type for nextsv.
 #[non_exhaustive]
 #[derive(Error, Debug)]
@@ -28,18 +64,88 @@ pub enum Error {
     /// The component must be a digit
     #[error("Version must be a number but found {0}")]
     MustBeNumber(String),
+    /// The version number has a leading zero
+    #[error("Version number {0} must not have a leading zero")]
+    LeadingZero(String),
+    /// The pre-release identifier is not valid
+    #[error("Pre-release identifier \"{0}\" is not valid")]
+    InvalidPreRelease(String),
+    /// The build metadata identifier is not valid
+    #[error("Build metadata identifier \"{0}\" is not valid")]
+    InvalidBuild(String),
+    /// The version does not have a pre-release suffix
+    #[error("Version {0} is not a pre-release")]
+    NotPreRelease(String),
     /// No valid version tag was found in the repository
     #[error("No valid version tag found in the repository")]
     NoVersionTag,
     /// The first production release (1.0.0) has already been made
     #[error("First production release already deployed. Current major version: {0}")]
     MajorAlreadyUsed(String),
+    /// The first production release must be at least 1.0.0
+    #[error("First production release must be at least 1.0.0 but {0} was given")]
+    FirstProductionTooLow(String),
+    /// The version given is not greater than the current version
+    #[error("Version {0} is not greater than the current version {1}")]
+    VersionNotGreater(String, String),
+    /// The version is tagged on different commits
+    #[error("Version {0} is tagged on different commits: {1} and {2}")]
+    AmbiguousTag(String, git2::Oid, git2::Oid),
+    /// The next version is not greater than an existing version tag
+    #[error("Next version {0} is not greater than the existing version tag {1}")]
+    VersionRegression(String, String),
+    /// The latest version tag does not match the version calculated
+    #[error("Latest version tag {0} does not match the version calculated from its commits: {1} ({2} change)")]
+    VerifyMismatch(String, String, String),
+    /// The walk of the commits took longer than the timeout
+    #[error("Walk of the commits timed out after {0:?}")]
+    WalkTimedOut(std::time::Duration),
+    /// The abbreviation of the commit id is not between 4 and 40
+    #[error("Abbreviation must be between 4 and 40 but {0} was given")]
+    InvalidAbbrev(usize),
+    /// The force option does not set a change level
+    #[error("Force option {0} does not set a change level")]
+    NotForceLevel(String),
+    /// The type-enum rule in the commitlint config is not valid
+    #[error("The commitlint type-enum rule must list the types but found {0}")]
+    InvalidCommitlintConfig(String),
+    /// The creation of the tag was not confirmed
+    #[error("Tag {0} was not created as it was not confirmed")]
+    TagNotConfirmed(String),
+    /// The tag glob does not have a single `*` for the version
+    #[error("Tag glob {0} must have a single `*` matching the version")]
+    InvalidTagGlob(String),
+    /// The tag template does not write the version once
+    #[error("Tag template {0} must write the version once as {{version}}, {{core}} or {{major}}.{{minor}}.{{patch}} and may only use {{prefix}} elsewhere")]
+    InvalidTagTemplate(String),
+    /// The tag template cannot name a pre-release
+    #[error("Tag template {0} leaves out the pre-release so cannot name {1}")]
+    PreReleaseNotInTemplate(String, String),
+    /// The repository is in the middle of a rebase or bisect
+    #[error("Repository has a {0} in progress so HEAD is not the branch to release. Finish or abort it first.")]
+    RepositoryBusy(String),
+    /// Issues were found in the version tags
+    #[error("{0} issue(s) found in the version tags")]
+    InconsistentTags(usize),
+    /// The environment variable is not set or is empty
+    #[error("Environment variable {0} is not set or is empty")]
+    MissingEnvVar(String),
     /// No conventional commits in the VersionCalculator struct
     #[error("No conventional commits have been loaded into the VersionCalculator struct. May have been called before `commits`.")]
     NoConventionalCommits,
-    /// Missing required file found.
-    #[error("Missing the required file(s): {0:?}.")]
-    MissingRequiredFile(Vec<OsString>),
+    /// Commits were found since the baseline but none are conventional
+    #[error("None of the {0} commit(s) since {1} are conventional commits")]
+    NoConventionalCommitsFound(u32, String),
+    /// Missing required file found with the level enforced and the top
+    /// type found.
+    #[error("Missing the required file(s): {0:?}. Enforcing at {1} level because the top type found is {2}.")]
+    MissingRequiredFile(Vec<OsString>, TypeHierarchy, TypeHierarchy),
+    /// The level does not bump the core version
+    #[error("Level {0} does not bump the core version")]
+    NotCoreLevel(String),
+    /// Not a valid level name.
+    #[error("{0} is not a valid level name.")]
+    NotLevelName(String),
     /// Not a valid Type Hierachy name.
     #[error("{0} is not a valid type hierarchy namne.")]
     NotTypeHierachyName(String),
@@ -52,28 +158,59 @@ pub enum Error {
     /// The minimum change level set for check has not been met.
     #[error("Minimum change level has not been met.")]
     MinimumChangeLevelNotMet,
+    /// Error passed up from std::io
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+    /// Error passed up from serde_json
+    #[error("{0}")]
+    Json(#[from] serde_json::Error),
     /// Error passed up from git2
-    #[error("0:?")]
+    #[error("{0}")]
     Git2(#[from] git2::Error),
 }
 
-impl From<Error> for Exit {
-    fn from(err: Error) -> Self {
-        match err {
+impl Error {
+    /// Convert the error to an Exit using the exit codes given
+    ///
+    pub fn into_exit(self, codes: &ExitCodes) -> Exit {
+        match self {
             Error::Git2(_) => {
-                Exit::new(Code::new(EXIT_NOT_CALCULATED_CODE)).with_message(err.to_string())
+                Exit::new(Code::new(codes.not_calculated)).with_message(self.to_string())
             }
-            Error::MissingRequiredFile(_) => {
-                Exit::new(Code::new(EXIT_MISSING_REQUIRED_CODE)).with_message(err.to_string())
+            Error::MissingRequiredFile(..) => {
+                Exit::new(Code::new(codes.missing_required)).with_message(self.to_string())
             }
             Error::NoFilesListed => {
-                Exit::new(Code::new(EXIT_NO_FILES_LISTED)).with_message(err.to_string())
+                Exit::new(Code::new(codes.no_files_listed)).with_message(self.to_string())
             }
-            Error::MinimumChangeLevelMet => Exit::new(Code::SUCCESS).with_message(err.to_string()),
+            Error::MinimumChangeLevelMet => Exit::new(Code::SUCCESS).with_message(self.to_string()),
             Error::MinimumChangeLevelNotMet => {
-                Exit::new(Code::new(EXIT_NOT_REQUIRED_LEVEL)).with_message(err.to_string())
+                Exit::new(Code::new(codes.not_required_level)).with_message(self.to_string())
             }
-            _ => Exit::new(Code::new(EXIT_UNEXPECTED_ERROR)),
+            _ => Exit::new(Code::new(codes.unexpected_error)).with_message(self.to_string()),
         }
     }
 }
+
+impl From<Error> for Exit {
+    fn from(err: Error) -> Self {
+        err.into_exit(&ExitCodes::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error as _;
+
+    use super::*;
+
+    #[test]
+    fn git2_error_is_displayed_and_chained_as_the_source() {
+        let error = Error::from(git2::Error::from_str("reference not found"));
+
+        assert_eq!("reference not found", error.to_string());
+        let source = error.source().unwrap();
+        let git2_error = source.downcast_ref::<git2::Error>().unwrap();
+        assert_eq!("reference not found", git2_error.message());
+    }
+}
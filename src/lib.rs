@@ -35,14 +35,48 @@
 //! #    Ok(())
 //! # }
 //! ```
+//!
+//! ## Repository discovery
+//!
+//! The repository is found from the working directory (or the path
+//! given) as git finds it, so nextsv runs from a subdirectory or from a
+//! linked worktree created with `git worktree add`. Submodules are not
+//! walked: the version is calculated for the repository containing the
+//! directory only, so from the outer repository the commits in a
+//! submodule are not counted.
 
+mod audit;
 mod calculator;
+mod changelog;
+mod commitlint;
 mod conventional;
 mod error;
+mod notes;
+mod plan;
 mod semantic;
+mod state;
+mod tag_glob;
+mod tag_template;
+#[cfg(feature = "test-util")]
+#[cfg_attr(docsrs, doc(cfg(feature = "test-util")))]
+pub mod test_util;
 
-pub use calculator::{Answer, ForceLevel, VersionCalculator};
+pub use audit::{TagAudit, TagIssue};
+pub use calculator::{
+    Answer, ForceLevel, ForceOptions, Previews, ReleaseTags, SkippedTag, TagListing, TagStatus,
+    VersionCalculator,
+};
+pub use changelog::{Changelog, ChangelogSort, Entry, Section};
+pub use commitlint::{CommitlintConfig, COMMITLINT_CONFIG};
 pub(crate) use conventional::ConventionalCommits;
-pub use conventional::TypeHierarchy;
-pub use error::Error;
-pub use semantic::{Level, Semantic};
+pub use conventional::{
+    known_types, DepsBump, ParsedCommit, RevertBump, TypeHierarchy, UnknownType,
+};
+pub use error::{Error, ExitCodes};
+pub use plan::{PlanMetrics, PlanOptions, ReleasePlan};
+pub use semantic::{
+    docker_tag, Identifier, Level, PartialSemantic, PreRelease, PreReleaseStyle, Semantic,
+};
+pub use state::State;
+pub use tag_glob::TagGlob;
+pub use tag_template::TagTemplate;
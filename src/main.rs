@@ -1,51 +1,246 @@
 use std::ffi::OsString;
-use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
-use clap::{Parser, ValueEnum};
-use nextsv::{Answer, Error, ForceLevel, TypeHierarchy, VersionCalculator};
+use clap::{Parser, Subcommand, ValueEnum};
+use is_terminal::IsTerminal;
+use nextsv::{
+    docker_tag, Answer, Changelog, ChangelogSort, CommitlintConfig, DepsBump, Error, ExitCodes,
+    ForceLevel, ForceOptions, Level, PreReleaseStyle, ReleaseTags, RevertBump, State, TagGlob,
+    TagTemplate, TypeHierarchy, UnknownType, VersionCalculator, COMMITLINT_CONFIG,
+};
 use proc_exit::{Code, ExitResult};
+use serde::Serialize;
 
 #[derive(ValueEnum, Debug, Clone)]
-enum ForceOptions {
-    Major,
-    Minor,
-    Patch,
-    First,
+enum EnvFileMode {
+    /// replace the contents of the file
+    Overwrite,
+    /// add to the end of the file
+    Append,
 }
 
-impl fmt::Display for ForceOptions {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            ForceOptions::Major => write!(f, "major"),
-            ForceOptions::Minor => write!(f, "minor"),
-            ForceOptions::Patch => write!(f, "patch"),
-            ForceOptions::First => write!(f, "first"),
+#[derive(ValueEnum, Debug, Clone, PartialEq, Eq)]
+enum ChangelogFormat {
+    /// markdown with a list of entries for each section
+    Markdown,
+    /// json with the sections and entries as arrays
+    Json,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    /// json lines with an object for each package giving the name, the
+    /// previous and next versions and the level
+    Jsonl,
+}
+
+/// The content reported by the program
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Output {
+    /// the next version number (the default)
+    Version,
+    /// the level of the change
+    Level,
+    /// the next version number and the level of the change, labelled
+    Both,
+    /// the next version number or, with no change to release, a
+    /// description of HEAD in the style of `git describe`
+    Describe,
+    /// the changelog for the next version
+    Changelog,
+    /// the candidate next versions at each level
+    Previews,
+    /// a json line for each package
+    Jsonl,
+}
+
+impl Output {
+    fn from_args(args: &Cli) -> Output {
+        if args.print_version_only {
+            return Output::Version;
+        }
+        if args.print_level_only {
+            return Output::Level;
+        }
+        if args.describe {
+            return Output::Describe;
+        }
+        if args.changelog {
+            return Output::Changelog;
+        }
+        if args.previews {
+            return Output::Previews;
+        }
+        if args.output == Some(OutputFormat::Jsonl) {
+            return Output::Jsonl;
+        }
+        match (args.number, args.level) {
+            (true, true) => Output::Both,
+            (false, true) => Output::Level,
+            (true, false) | (false, false) => Output::Version,
         }
     }
 }
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// List every tag showing how it parsed and the latest version tag
+    Tags,
+    /// Check the version tags parse, have no gaps and are tagged in order
+    ///
+    /// Also reports the pre-releases that were never released before a
+    /// later release.
+    Audit {
+        /// Exit with an error if any issue is found
+        #[arg(long)]
+        strict: bool,
+    },
+}
+
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
     #[clap(flatten)]
     logging: clap_verbosity_flag::Verbosity,
     /// Force the calculation of the version number
     #[arg(short, long, value_enum)]
     force: Option<ForceOptions>,
+    /// Force the change at the level read from a file (e.g. "minor")
+    ///
+    /// Applied as if `--force` were given with the level.
+    #[arg(long, value_name = "PATH", conflicts_with = "force")]
+    level_from: Option<PathBuf>,
+    /// Version of the first production release for `--force first`
+    ///
+    /// Defaults to 1.0.0. Must be at least 1.0.0 and greater than the
+    /// current version.
+    #[arg(long, value_name = "VERSION")]
+    first_version: Option<String>,
     /// Prefix string to identify version number tags
-    #[arg(short, long, value_parser, default_value = "v")]
-    prefix: String,
+    ///
+    /// Defaults to the `nextsv.tagPrefix` value in the git config
+    /// or "v" if it is not set.
+    #[arg(short, long, value_parser)]
+    prefix: Option<String>,
+    /// Match the version prefix ignoring case (e.g. `V1.2.3` for "v")
+    ///
+    /// The prefix of the tag found is used for the next version.
+    #[arg(long)]
+    prefix_case_insensitive: bool,
+    /// Find version tags with a glob instead of the prefix
+    ///
+    /// The single `*` matches the version number and the text before it
+    /// is the prefix, e.g. `release/v*` or `v*-stable`.
+    #[arg(long, value_name = "PATTERN", value_parser = tag_glob, conflicts_with_all = ["prefix", "prefix_case_insensitive", "state_file"])]
+    tag_glob: Option<TagGlob>,
+    /// Name the version tags with a template (e.g. `release/{prefix}{version}`)
+    ///
+    /// The placeholders are `{prefix}`, `{version}`, `{core}`, `{major}`,
+    /// `{minor}` and `{patch}`. The version is written once and the tags
+    /// are found with the equivalent glob (e.g. `release/v*`).
+    #[arg(long, value_name = "TEMPLATE", conflicts_with_all = ["tag_glob", "prefix_case_insensitive", "state_file"])]
+    tag_template: Option<String>,
+    /// Only use the version tags on the branch of HEAD
+    ///
+    /// Tags on commits not reachable from HEAD (e.g. on other release
+    /// branches) are ignored when finding the latest version.
+    #[arg(long, conflicts_with = "state_file")]
+    branch_tags_only: bool,
+    /// Kind of version tags used as the baseline
+    ///
+    /// For example lightweight tags can mark development milestones
+    /// while annotated tags mark the releases.
+    #[arg(long, value_enum, default_value = "any", conflicts_with = "state_file")]
+    release_tags: ReleaseTags,
     /// Report the level of the version number change
+    ///
+    /// Combined with `--number` both are reported with labels.
     #[arg(long)]
     level: bool,
     /// Report the version number
+    ///
+    /// The version number is reported if no output is selected.
     #[arg(long)]
     number: bool,
+    /// Report only the version number
+    #[arg(long, conflicts_with_all = ["level", "number", "print_level_only"])]
+    print_version_only: bool,
+    /// Report only the level of the version number change
+    #[arg(long, visible_alias = "bump-only", conflicts_with_all = ["level", "number"])]
+    print_level_only: bool,
+    /// Report HEAD in the style of `git describe` (e.g. v1.2.3-5-gabcdef1)
+    /// when there is no change to release
+    ///
+    /// The next version number is reported if there is a change.
+    #[arg(long, conflicts_with_all = ["level", "number", "print_version_only", "print_level_only"])]
+    describe: bool,
+    /// Report the changelog for the next version
+    ///
+    /// The commits are grouped in sections by type, dated with the
+    /// HEAD commit.
+    #[arg(long, conflicts_with_all = ["level", "number", "print_version_only", "print_level_only", "describe", "verify"])]
+    changelog: bool,
+    /// Format of the changelog
+    #[arg(long, value_enum, default_value = "markdown", requires = "changelog")]
+    changelog_format: ChangelogFormat,
+    /// Order of the entries within each section of the changelog
+    #[arg(long, value_enum, default_value = "newest", requires = "changelog")]
+    changelog_sort: ChangelogSort,
+    /// Link the changelog heading to the comparison of the previous and
+    /// next tags on the forge at the repository URL given (e.g.
+    /// `https://github.com/owner/repo`)
+    ///
+    /// The URL is `<BASE>/compare/<previous>...<next>`. The link is left
+    /// out if the previous version is not tagged.
+    #[arg(long, value_name = "BASE", requires = "changelog")]
+    compare_url: Option<String>,
+    /// Report the candidate next versions for a patch, minor and major
+    /// bump whatever the commits found
+    #[arg(long, conflicts_with_all = ["level", "number", "print_version_only", "print_level_only", "describe", "changelog", "verify"])]
+    previews: bool,
+    /// Report the result in a machine readable format
+    ///
+    /// The repository is reported as a single package named by the
+    /// directory of the working tree.
+    #[arg(long, value_enum, value_name = "FORMAT", conflicts_with_all = ["level", "number", "print_version_only", "print_level_only", "describe", "changelog", "previews", "verify"])]
+    output: Option<OutputFormat>,
+    /// Write the output to this file instead of stdout
+    ///
+    /// Parent directories are created if needed.
+    #[arg(long, value_name = "PATH")]
+    output_file: Option<PathBuf>,
+    /// Do not end the output with a newline
+    ///
+    /// Useful when capturing the output with `$(...)`.
+    #[arg(long)]
+    no_newline: bool,
     /// Require changes to these file before building release
     #[arg(short, long)]
     require: Vec<OsString>,
+    /// Read more required files from the file, one path per line
+    ///
+    /// Blank lines and lines starting with `#` are ignored. The files are
+    /// required as well as any given with `--require`.
+    #[arg(long, value_name = "PATH")]
+    require_file: Option<PathBuf>,
     /// Level at which required files should be enforced
     #[clap(short, long, default_value = "feature")]
     enforce_level: TypeHierarchy,
+    /// Only enforce required files when a release would be made
+    #[arg(long)]
+    require_only_on_release: bool,
+    /// Fail if there are commits since the baseline but none are
+    /// conventional commits
+    ///
+    /// Otherwise a warning is reported on stderr.
+    #[arg(long)]
+    fail_on_no_conventional: bool,
     /// Check level meets minimum for setting
     ///
     /// This option can be used to check the calculated level
@@ -56,6 +251,267 @@ struct Cli {
     /// add outupt to environment variable
     #[clap(long, default_value = "NEXTSV_LEVEL")]
     set_env: Option<String>,
+    /// Environment variable to set to the version
+    #[arg(long, value_name = "NAME", default_value = "NEXTSV_VERSION")]
+    set_version_env: Option<String>,
+    /// Read release directives from git notes in the ref
+    ///
+    /// A note on a commit can set the level at which the commit is
+    /// counted (`bump: major|minor|patch`) or exclude the commit from
+    /// the calculation (`skip: true`).
+    #[arg(long, value_name = "REF")]
+    read_notes: Option<String>,
+    /// Write the version and level to a dotenv style file
+    ///
+    /// The file contains `NEXTSV_VERSION=<version>` and
+    /// `NEXTSV_LEVEL=<level>` lines and can be used with `source`
+    /// or as a GitHub Actions environment file.
+    #[arg(long, value_name = "PATH")]
+    env_file: Option<PathBuf>,
+    /// Overwrite or append to the env file
+    #[arg(long, value_enum, default_value = "overwrite")]
+    env_file_mode: EnvFileMode,
+    /// Promote to the first production release (1.0.0) only if a
+    /// breaking change is found
+    ///
+    /// Without a breaking change the next version is calculated
+    /// applying the rules for versions before 1.0.0.
+    #[arg(long, conflicts_with = "force")]
+    promote_if_breaking: bool,
+    /// Start a pre-release series with the label (e.g. `rc`)
+    ///
+    /// The core version is bumped first and the first pre-release
+    /// is attached, e.g. a feature on v1.2.0 reports v1.3.0-rc.1.
+    #[arg(long, value_name = "LABEL", conflicts_with = "promote_if_breaking")]
+    pre_release: Option<String>,
+    /// Use the value of the environment variable as the pre-release
+    /// counter (e.g. `GITHUB_RUN_NUMBER` for `v1.3.0-ci.427`)
+    ///
+    /// The counter is not continued from the existing tags.
+    #[arg(long, value_name = "VAR", requires = "pre_release")]
+    pre_release_from_env: Option<String>,
+    /// Restart the pre-release series at 1 regardless of the existing
+    /// tags (e.g. v1.2.0-rc.1 after abandoning v1.2.0-rc.5)
+    #[arg(
+        long,
+        requires = "pre_release",
+        conflicts_with = "pre_release_from_env"
+    )]
+    reset_prerelease: bool,
+    /// Report the development version following the release (e.g.
+    /// v1.3.0-dev.0 after v1.2.0) with the stage as the pre-release
+    ///
+    /// The tag, state file and environment variables are for the release.
+    #[arg(long, value_name = "STAGE", conflicts_with_all = ["verify", "describe"])]
+    post_release_dev: Option<String>,
+    /// Only count breaking changes with no scope or this scope
+    ///
+    /// Breaking changes scoped to another area are counted at the
+    /// level of their type as if they were not breaking.
+    #[arg(long, value_name = "SCOPE")]
+    breaking_requires_scope: Option<String>,
+    /// Explain the calculation on stderr
+    ///
+    /// Reports the tag used as the baseline (full ref name and commit)
+    /// and the levels calculated.
+    #[arg(long)]
+    explain: bool,
+    /// Print a summary of the commits since the baseline tag to stderr
+    ///
+    /// For example: "12 commits since v1.2.3 (3 feat, 2 fix, 7 other; 1 breaking)".
+    #[arg(long)]
+    summary: bool,
+    /// Skip commits with a message containing the marker
+    ///
+    /// Repeat for more markers. Give an empty marker to skip no commits.
+    #[arg(long, value_name = "MARKER", default_value = "[skip release]")]
+    skip_marker: Vec<String>,
+    /// Ignore commits of these conventional types
+    #[arg(long, value_name = "TYPE", value_delimiter = ',', value_parser = known_type)]
+    ignore_types: Vec<String>,
+    /// Count commits of these conventional types at most once
+    #[arg(long, value_name = "TYPE", value_delimiter = ',', value_parser = known_type)]
+    collapse_types: Vec<String>,
+    /// Only count commits of the types listed in the `type-enum` rule of
+    /// a commitlint config
+    ///
+    /// Defaults to `.commitlintrc.json` if no path is given. The built-in
+    /// types are used if the file or the rule is absent.
+    #[arg(long, value_name = "PATH", num_args = 0..=1, default_missing_value = COMMITLINT_CONFIG)]
+    commitlint_config: Option<PathBuf>,
+    /// Read the current version from a state file instead of tags
+    ///
+    /// The JSON state file records the last released version, the
+    /// prefix and the commit released (e.g. `.nextsv-state.json`). The
+    /// state is updated when the tag is created by `--tag`.
+    #[arg(long, value_name = "PATH")]
+    state_file: Option<PathBuf>,
+    /// Update the state file with the next version released at HEAD
+    ///
+    /// Updates the state without creating a tag, for repositories that
+    /// are not tagged.
+    #[arg(long, requires = "state_file")]
+    update_state: bool,
+    /// Count a breaking change for commits that are not conventional
+    /// but have a `BREAKING CHANGE:` footer in the message
+    #[arg(long)]
+    breaking_footer_always: bool,
+    /// Verify the latest version tag matches the version calculated
+    /// from the commits since the previous version tag
+    #[arg(long)]
+    verify: bool,
+    /// Bump the patch number for every change before 1.0.0
+    ///
+    /// Takes precedence over a breaking change, which otherwise
+    /// bumps the minor number before 1.0.0.
+    #[arg(long)]
+    zero_feat_as_patch: bool,
+    /// Apply the same rules before 1.0.0 as from 1.0.0
+    ///
+    /// A breaking change bumps the major number (e.g. v0.3.0 to v1.0.0)
+    /// and a feature the minor number. Takes precedence over
+    /// `--zero-feat-as-patch`.
+    #[arg(long)]
+    semver_strict: bool,
+    /// Bump the minor number only for at least this many feature commits
+    ///
+    /// Fewer feature commits are counted as a patch change.
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    min_feats_for_minor: Option<u32>,
+    /// Abort the walk of the commits after this many seconds
+    #[arg(long, value_name = "SECS")]
+    timeout: Option<u64>,
+    /// Count the conventional commits listed as bullet lines in the body
+    /// of squash commits (e.g. `- feat: add a feature`)
+    #[arg(long)]
+    expand_squash_body: bool,
+    /// How dependency update commits (type or scope `deps`) are counted
+    #[arg(long, value_enum, default_value = "patch")]
+    deps_bump: DepsBump,
+    /// How revert commits (type `revert`) are counted
+    #[arg(long, value_enum, default_value = "fix")]
+    revert_bump: RevertBump,
+    /// How conventional commits with an unknown type (e.g. `security:`)
+    /// are counted
+    #[arg(long, value_enum, default_value = "patch")]
+    unknown_type: UnknownType,
+    /// Count commits of a type at the level given (e.g. `perf=feature`)
+    ///
+    /// The levels are other, fix and feature. By default `perf` and the
+    /// other types without a release meaning are other, `fix` and
+    /// `revert` are fix and `feat` is feature. Commits of a type at the
+    /// feature level bump the minor number.
+    #[arg(long, value_name = "TYPE=LEVEL", value_parser = type_level)]
+    type_level: Vec<(String, TypeHierarchy)>,
+    /// Style of the pre-release suffix in version tags
+    #[arg(long, value_enum, default_value = "sem-ver")]
+    pre_release_style: PreReleaseStyle,
+    /// Append the abbreviated HEAD commit id to the version reported as
+    /// build metadata (e.g. v1.3.0+1a2b3c4)
+    #[arg(long)]
+    build_hash: bool,
+    /// Report the version as a docker image tag
+    ///
+    /// Characters other than ASCII letters, digits, `_`, `.` and `-`
+    /// (e.g. the `+` before build metadata) are replaced with `-`, leading
+    /// `.` and `-` are removed and the tag is truncated to 128 characters.
+    #[arg(long)]
+    docker_tag: bool,
+    /// Report the version without the prefix (e.g. 1.2.3 for v1.2.3)
+    ///
+    /// Applies to every output, the environment variable and the env
+    /// file. The tag created by `--tag` keeps the prefix.
+    #[arg(long)]
+    strip_prefix: bool,
+    /// Report the version with the prefix instead of the tag prefix
+    ///
+    /// For example `--prefix '' --display-prefix v` finds and creates
+    /// tags such as `1.2.3` and reports `v1.2.3`. Applies to the same
+    /// outputs as `--strip-prefix`.
+    #[arg(long, value_name = "PREFIX", conflicts_with = "strip_prefix")]
+    display_prefix: Option<String>,
+    /// Number of hex characters of the commit id in the build metadata
+    ///
+    /// Defaults to git's rules for abbreviating commit ids.
+    #[arg(long, value_name = "N", requires = "build_hash", value_parser = clap::value_parser!(u8).range(4..=40))]
+    abbrev: Option<u8>,
+    /// Fail if a version is tagged on different commits
+    ///
+    /// Without this option a warning is logged.
+    #[arg(long)]
+    strict_tags: bool,
+    /// Only count commits on HEAD that are not on the base ref
+    ///
+    /// The walk stops at the merge-base of HEAD and the ref (e.g.
+    /// `origin/main`) so the bump reflects just the commits on the branch.
+    #[arg(long, value_name = "REF")]
+    base_ref: Option<String>,
+    /// Only count commits on the second ref that are not on the first
+    ///
+    /// The walk starts at the second ref (the tip) and stops at the
+    /// merge-base of the two refs (e.g. `main release/1.x`).
+    #[arg(long, num_args = 2, value_names = ["REF", "TIP"], conflicts_with_all = ["base_ref", "verify"])]
+    merge_base: Option<Vec<String>>,
+    /// Ignore this commit and its ancestors (e.g. an imported history)
+    ///
+    /// Sets a lower boundary for the walk independent of the version tag.
+    /// Use with `--state-file` in a repository without version tags.
+    #[arg(long, value_name = "COMMIT")]
+    baseline_commit: Option<String>,
+    /// Create a tag for the next version at HEAD
+    ///
+    /// Asks for confirmation on stderr unless `--yes` is given. Without
+    /// a terminal to ask the tag is not created. The tag is created after
+    /// the checks and before anything is written, so if the tag is not
+    /// created the version is not reported.
+    #[arg(long, conflicts_with_all = ["verify", "check"])]
+    tag: bool,
+    /// Create the tag without asking for confirmation
+    #[arg(short, long, visible_alias = "assume-yes", requires = "tag")]
+    yes: bool,
+    /// Exit code when there is no change to release
+    #[arg(long, value_name = "CODE", help_heading = "Exit codes")]
+    exit_code_no_change: Option<i32>,
+    /// Exit code for an unexpected error
+    #[arg(long, value_name = "CODE", help_heading = "Exit codes")]
+    exit_code_error: Option<i32>,
+    /// Exit code when the version could not be calculated
+    #[arg(long, value_name = "CODE", help_heading = "Exit codes")]
+    exit_code_not_calculated: Option<i32>,
+    /// Exit code when required files are missing
+    #[arg(long, value_name = "CODE", help_heading = "Exit codes")]
+    exit_code_missing_required: Option<i32>,
+    /// Exit code when the check level is not met
+    #[arg(long, value_name = "CODE", help_heading = "Exit codes")]
+    exit_code_not_required_level: Option<i32>,
+    /// Exit code when no files have been listed
+    #[arg(long, value_name = "CODE", help_heading = "Exit codes")]
+    exit_code_no_files_listed: Option<i32>,
+}
+
+impl Cli {
+    /// The exit codes with any overrides from the command line applied
+    ///
+    fn exit_codes(&self) -> ExitCodes {
+        let mut codes = ExitCodes::default();
+        let overrides = [
+            (self.exit_code_no_change, &mut codes.no_change),
+            (self.exit_code_error, &mut codes.unexpected_error),
+            (self.exit_code_not_calculated, &mut codes.not_calculated),
+            (self.exit_code_missing_required, &mut codes.missing_required),
+            (
+                self.exit_code_not_required_level,
+                &mut codes.not_required_level,
+            ),
+            (self.exit_code_no_files_listed, &mut codes.no_files_listed),
+        ];
+        for (value, code) in overrides {
+            if let Some(value) = value {
+                *code = value;
+            }
+        }
+        codes
+    }
 }
 
 fn main() {
@@ -69,32 +525,333 @@ fn run() -> ExitResult {
     let mut builder = get_logging(args.logging.log_level_filter());
     builder.init();
 
-    match (args.number, args.level) {
-        (false, false) => log::info!("Calculating the next version level"),
-        (false, true) => log::info!("Calculating the next version level"),
-        (true, false) => log::info!("Calculating the next version number"),
-        (true, true) => log::info!("Calculating the next version number and level"),
+    let exit_codes = args.exit_codes();
+    log::trace!("exit codes: {:?}", &exit_codes);
+
+    match &args.command {
+        Some(Command::Tags) => return list_tags(&args).map_err(|e| e.into_exit(&exit_codes)),
+        Some(Command::Audit { strict }) => {
+            return audit_tags(&args, *strict).map_err(|e| e.into_exit(&exit_codes))
+        }
+        None => {}
+    }
+
+    match execute(&args) {
+        Ok(answer) if answer.bump_level == Level::None => {
+            log::debug!("no change to release");
+            Code::new(exit_codes.no_change).ok()
+        }
+        Ok(_) => Code::SUCCESS.ok(),
+        Err(e) => Err(e.into_exit(&exit_codes)),
+    }
+}
+
+/// The version prefix given or configured
+///
+fn version_prefix(args: &Cli) -> Result<String, Error> {
+    let prefix = match &args.prefix {
+        Some(prefix) => prefix.clone(),
+        None => VersionCalculator::configured_prefix(".")?.unwrap_or_else(|| "v".to_string()),
     };
+    log::debug!("version prefix is {:?}", &prefix);
+    Ok(prefix)
+}
 
-    let latest_version = VersionCalculator::new(&args.prefix)?;
+/// List the tags for the `tags` subcommand
+///
+fn list_tags(args: &Cli) -> Result<(), Error> {
+    let listing = VersionCalculator::list_tags_in(
+        ".",
+        &version_prefix(args)?,
+        args.pre_release_style,
+        args.prefix_case_insensitive,
+    )?;
+    for tag in listing {
+        println!("{}", tag);
+    }
+    Ok(())
+}
+
+/// Report the issues in the version tags for the `audit` subcommand
+///
+fn audit_tags(args: &Cli, strict: bool) -> Result<(), Error> {
+    let audit = VersionCalculator::audit_tags_in(
+        ".",
+        &version_prefix(args)?,
+        args.pre_release_style,
+        args.prefix_case_insensitive,
+    )?;
+    println!("{}", audit);
+    if strict && !audit.is_consistent() {
+        return Err(Error::InconsistentTags(audit.issues.len()));
+    }
+    Ok(())
+}
+
+fn execute(args: &Cli) -> Result<Answer, Error> {
+    let output = Output::from_args(args);
+    match output {
+        Output::Version => log::info!("Calculating the next version number"),
+        Output::Level => log::info!("Calculating the next version level"),
+        Output::Both => log::info!("Calculating the next version number and level"),
+        Output::Describe => log::info!("Calculating the next version number or describing HEAD"),
+        Output::Changelog => log::info!("Calculating the changelog for the next version"),
+        Output::Previews => log::info!("Calculating the candidate next versions"),
+        Output::Jsonl => log::info!("Calculating the next version of each package"),
+    };
+
+    let mut latest_version = match (&args.state_file, &args.tag_glob) {
+        (Some(path), _) => {
+            log::debug!("reading the current version from {:?}", path);
+            VersionCalculator::from_state(".", &State::read(path)?)?
+        }
+        (None, Some(glob)) => VersionCalculator::new_tag_glob(glob)?,
+        (None, None) if args.tag_template.is_some() => {
+            let template = args.tag_template.as_deref().unwrap_or_default();
+            VersionCalculator::new_tag_template(&TagTemplate::new(
+                template,
+                &version_prefix(args)?,
+            )?)?
+        }
+        (None, None) => {
+            let prefix = version_prefix(args)?;
+            if args.prefix_case_insensitive {
+                VersionCalculator::new_ignore_case(&prefix)?
+            } else {
+                VersionCalculator::new(&prefix)?
+            }
+        }
+    };
+    if args.pre_release_style != PreReleaseStyle::SemVer {
+        latest_version = latest_version.pre_release_style(args.pre_release_style)?;
+    }
+    if args.branch_tags_only {
+        latest_version = latest_version.branch_tags_only()?;
+    }
+    if args.release_tags != ReleaseTags::Any {
+        latest_version = latest_version.release_tags(args.release_tags)?;
+    }
+    if let Some(notes_ref) = &args.read_notes {
+        latest_version = latest_version.read_notes(notes_ref);
+    }
+    if let Some(scope) = &args.breaking_requires_scope {
+        latest_version = latest_version.breaking_requires_scope(scope);
+    }
+    if args.strict_tags {
+        latest_version.check_tags()?;
+    }
+    latest_version = latest_version.skip_markers(args.skip_marker.clone());
+    if !args.ignore_types.is_empty() {
+        latest_version = latest_version.ignore_types(args.ignore_types.clone());
+    }
+    if !args.collapse_types.is_empty() {
+        latest_version = latest_version.collapse_types(args.collapse_types.clone());
+    }
+    if let Some(path) = &args.commitlint_config {
+        if let Some(types) = CommitlintConfig::read(path)?.types() {
+            latest_version = latest_version.allowed_types(types.to_vec());
+        }
+    }
+    if args.breaking_footer_always {
+        latest_version = latest_version.breaking_footer_always();
+    }
+    if args.expand_squash_body {
+        latest_version = latest_version.expand_squash_body();
+    }
+    if args.deps_bump != DepsBump::Patch {
+        latest_version = latest_version.deps_bump(args.deps_bump);
+    }
+    if args.revert_bump != RevertBump::Fix {
+        latest_version = latest_version.revert_bump(args.revert_bump);
+    }
+    if args.unknown_type != UnknownType::Patch {
+        latest_version = latest_version.unknown_type(args.unknown_type);
+    }
+    for (type_, level) in &args.type_level {
+        latest_version = latest_version.type_level(type_, level.clone());
+    }
+    if args.zero_feat_as_patch {
+        latest_version = latest_version.zero_feat_as_patch();
+    }
+    if args.semver_strict {
+        latest_version = latest_version.semver_strict();
+    }
+    if let Some(count) = args.min_feats_for_minor {
+        latest_version = latest_version.min_feats_for_minor(count);
+    }
+    if args.reset_prerelease {
+        latest_version = latest_version.reset_pre_release();
+    }
+    if let Some(timeout) = args.timeout {
+        latest_version = latest_version.timeout(Duration::from_secs(timeout));
+    }
+    if let Some(rev) = &args.baseline_commit {
+        latest_version = latest_version.baseline_commit(rev);
+    }
+    if let Some(base_ref) = &args.base_ref {
+        latest_version = latest_version.base_ref(base_ref);
+    }
+    if let Some([other, tip]) = args.merge_base.as_deref() {
+        latest_version = latest_version.merge_base(other, tip);
+    }
 
-    log::trace!("require: {:#?}", args.require);
+    if args.verify {
+        let resp = latest_version.verify()?;
+        log::info!("latest version tag {} verified", &resp.version_number);
+        print_output(args, output, &resp, None, None)?;
+        return Ok(resp);
+    }
 
-    // Encapsulate the list of required files in an option
-    let files = if args.require.is_empty() {
-        Option::None
+    let build = if args.build_hash {
+        Some(latest_version.head_short_id(args.abbrev.map(usize::from))?)
     } else {
-        Option::Some(args.require)
+        None
     };
 
-    let resp = calculate(latest_version, args.force, files, args.enforce_level)?;
+    let (latest_version, resp) = calculate(latest_version, args)?;
+
+    check_level(args.check.clone(), resp.change_level())?;
+
+    let display_prefix = display_prefix(args);
+    let mut published = resp.clone();
+    if let Some(prefix) = display_prefix {
+        published.version_number.set_version_prefix(prefix);
+    }
+    let report = match output {
+        Output::Describe if resp.bump_level == Level::None => {
+            let describe = latest_version.describe(args.abbrev.map(usize::from))?;
+            let prefix = resp.version_number.version_prefix();
+            match (describe.strip_prefix(prefix), display_prefix) {
+                (Some(bare), Some(display)) => Some(format!("{}{}", display, bare)),
+                _ => Some(describe),
+            }
+        }
+        Output::Changelog => {
+            let compare_url = match &args.compare_url {
+                Some(base) => latest_version.compare_url(base, &resp.version_number)?,
+                None => None,
+            };
+            Some(changelog(&latest_version, &published, compare_url, args)?)
+        }
+        Output::Previews => Some(previews(&latest_version, display_prefix)),
+        Output::Jsonl => Some(package_line(&latest_version, &published, display_prefix)?),
+        _ => None,
+    };
 
-    set_environment_variable(args.set_env, resp.bump_level.to_string().into());
-    check_level(args.check, resp.change_level())?;
+    // creating the tag is the commit point, so nothing is written or
+    // reported if the tag is not created
+    if args.tag {
+        tag_version(&latest_version, &resp, args.yes)?;
+    }
+    if let (Some(path), true) = (&args.state_file, args.tag || args.update_state) {
+        if resp.bump_level != Level::None {
+            log::debug!("updating the state in {:?}", path);
+            latest_version.release_state(&resp)?.write(path)?;
+        }
+    }
+    set_environment_variable(args.set_env.clone(), resp.bump_level.to_string().into());
+    set_environment_variable(
+        args.set_version_env.clone(),
+        published.version_number.to_string().into(),
+    );
+    if let Some(path) = &args.env_file {
+        write_env_file(path, &args.env_file_mode, &published)?;
+    }
+    let mut reported = published.clone();
+    if let Some(stage) = &args.post_release_dev {
+        reported.version_number = published.version_number.post_release_dev(stage)?;
+        log::debug!(
+            "development version after the release is {}",
+            &reported.version_number
+        );
+    }
     log::debug!("not checking so print the output");
-    print_output(args.number, args.level, resp);
+    print_output(args, output, &reported, build.as_deref(), report)?;
+
+    Ok(resp)
+}
 
-    Code::SUCCESS.ok()
+fn changelog(
+    calculator: &VersionCalculator,
+    response: &Answer,
+    compare_url: Option<String>,
+    args: &Cli,
+) -> Result<String, Error> {
+    let mut changelog = Changelog::new_sorted(
+        &response.version_number,
+        calculator.commits(),
+        args.changelog_sort,
+    )
+    .with_date(&calculator.head_date()?);
+    if let Some(url) = compare_url {
+        changelog = changelog.with_compare_url(&url);
+    }
+    match args.changelog_format {
+        ChangelogFormat::Markdown => Ok(changelog.to_string().trim_end().to_string()),
+        ChangelogFormat::Json => changelog.to_json(),
+    }
+}
+
+/// The prefix of the versions reported if it differs from the tag prefix
+///
+fn display_prefix(args: &Cli) -> Option<&str> {
+    if args.strip_prefix {
+        Some("")
+    } else {
+        args.display_prefix.as_deref()
+    }
+}
+
+/// The result for a package reported as a json line
+///
+#[derive(Serialize)]
+struct PackageLine {
+    name: String,
+    previous: String,
+    next: String,
+    level: String,
+}
+
+/// Report the repository as a single package on a json line
+///
+fn package_line(
+    calculator: &VersionCalculator,
+    response: &Answer,
+    display_prefix: Option<&str>,
+) -> Result<String, Error> {
+    let repo = calculator.repository()?;
+    let name = repo
+        .workdir()
+        .and_then(|path| path.file_name())
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let mut previous = calculator.name();
+    if let Some(prefix) = display_prefix {
+        previous.set_version_prefix(prefix);
+    }
+    let line = PackageLine {
+        name,
+        previous: previous.to_string(),
+        next: response.version_number.to_string(),
+        level: response.bump_level.to_string(),
+    };
+    Ok(serde_json::to_string(&line)?)
+}
+
+fn previews(calculator: &VersionCalculator, display_prefix: Option<&str>) -> String {
+    let previews: Vec<String> = calculator
+        .previews()
+        .labelled()
+        .iter()
+        .map(|(label, version)| {
+            let mut version = (*version).clone();
+            if let Some(prefix) = display_prefix {
+                version.set_version_prefix(prefix);
+            }
+            format!("{}: {}", label, version)
+        })
+        .collect();
+    previews.join("\n")
 }
 
 fn check_level(threshold: Option<TypeHierarchy>, change_level: TypeHierarchy) -> Result<(), Error> {
@@ -114,37 +871,192 @@ fn check_level(threshold: Option<TypeHierarchy>, change_level: TypeHierarchy) ->
 
 fn set_environment_variable(env_variable: Option<String>, value: OsString) {
     if let Some(key) = env_variable {
+        log::info!("setting {}={:?}", key, value);
         std::env::set_var(key, value)
     }
 }
 
+fn write_env_file(path: &Path, mode: &EnvFileMode, answer: &Answer) -> Result<(), Error> {
+    let mut file = match mode {
+        EnvFileMode::Overwrite => File::create(path)?,
+        EnvFileMode::Append => OpenOptions::new().create(true).append(true).open(path)?,
+    };
+    log::debug!("writing env file {:?}", path);
+    writeln!(file, "NEXTSV_VERSION={}", answer.version_number)?;
+    writeln!(file, "NEXTSV_LEVEL={}", answer.bump_level)?;
+    Ok(())
+}
+
+/// Read the required files listed in the file, one path per line
+///
+/// Blank lines and `#` comments are skipped.
+///
+fn read_require_file(path: &Path) -> Result<Vec<OsString>, Error> {
+    let content = std::fs::read_to_string(path)?;
+    let required: Vec<OsString> = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(OsString::from)
+        .collect();
+    log::debug!("{} required file(s) read from {:?}", required.len(), path);
+    Ok(required)
+}
+
+/// Parse a glob identifying version tags
+///
+fn tag_glob(s: &str) -> Result<TagGlob, String> {
+    TagGlob::new(s).map_err(|e| e.to_string())
+}
+
+/// Parse the level for a commit type (e.g. `perf=feature`)
+///
+fn type_level(s: &str) -> Result<(String, TypeHierarchy), String> {
+    let (type_, level) = s
+        .split_once('=')
+        .ok_or_else(|| "expected TYPE=LEVEL".to_string())?;
+    let level = TypeHierarchy::from_str(level, true)?;
+    if level == TypeHierarchy::Breaking {
+        return Err("a type cannot be set to the breaking level".to_string());
+    }
+    Ok((type_.to_lowercase(), level))
+}
+
+/// Parse a conventional commit type recognised by nextsv
+///
+fn known_type(s: &str) -> Result<String, String> {
+    let commit_type = s.to_lowercase();
+    if nextsv::known_types().contains(&commit_type.as_str()) {
+        Ok(commit_type)
+    } else {
+        Err(format!(
+            "unknown type (expected one of: {})",
+            nextsv::known_types().join(", ")
+        ))
+    }
+}
+
 fn calculate(
     mut latest_version: VersionCalculator,
-    force: Option<ForceOptions>,
-    files: Option<Vec<OsString>>,
-    enforce_level: TypeHierarchy,
-) -> Result<Answer, Error> {
-    if let Some(f) = &force {
+    args: &Cli,
+) -> Result<(VersionCalculator, Answer), Error> {
+    if let Some(f) = &args.force {
         log::debug!("Force option set to {}", f);
     };
     latest_version = latest_version.walk_commits()?;
-    if let Some(f) = files {
-        latest_version.has_required(f, enforce_level)?;
+    if args.summary {
+        eprintln!("{}", latest_version.range_summary());
+    }
+    if latest_version.distance() > 0 && latest_version.conventional_count() == 0 {
+        let error = Error::NoConventionalCommitsFound(
+            latest_version.distance(),
+            latest_version.baseline_tag(),
+        );
+        if args.fail_on_no_conventional {
+            return Err(error);
+        }
+        eprintln!("warning: {}", error);
     }
-    let mut answer = if let Some(svc) = force {
-        match svc {
-            ForceOptions::Major => latest_version.force(ForceLevel::Major).next_version(),
-            ForceOptions::Minor => latest_version.force(ForceLevel::Minor).next_version(),
-            ForceOptions::Patch => latest_version.force(ForceLevel::Patch).next_version(),
-            ForceOptions::First => latest_version.promote_first()?,
+
+    let mut answer = if let Some(ForceOptions::First) = &args.force {
+        match &args.first_version {
+            Some(version) => latest_version.promote_first_to(version)?,
+            None => latest_version.promote_first()?,
         }
+    } else if args.promote_if_breaking {
+        latest_version.promote_if_breaking()?
     } else {
-        latest_version.next_version()
+        if let Some(option) = args.force {
+            latest_version.force(ForceLevel::try_from(option)?);
+        }
+        if let Some(path) = &args.level_from {
+            let level: Level = std::fs::read_to_string(path)?.parse()?;
+            log::debug!("level {} read from {:?}", level, path);
+            latest_version.force(ForceLevel::try_from(level)?);
+        }
+        match (&args.pre_release, &args.pre_release_from_env) {
+            (Some(label), Some(var)) => {
+                let counter = std::env::var(var).unwrap_or_default();
+                if counter.is_empty() {
+                    return Err(Error::MissingEnvVar(var.clone()));
+                }
+                log::debug!("pre-release counter {} read from {}", counter, var);
+                latest_version.next_pre_release_numbered(label, &counter)?
+            }
+            (Some(label), None) => latest_version.next_pre_release(label)?,
+            (None, _) => latest_version.next_version(),
+        }
     };
 
     answer.change_level = latest_version.top_level();
 
-    Ok(answer)
+    let mut required = args.require.clone();
+    if let Some(path) = &args.require_file {
+        required.extend(read_require_file(path)?);
+    }
+    log::trace!("require: {:#?}", required);
+    if !required.is_empty() {
+        if args.require_only_on_release && answer.bump_level == Level::None {
+            log::debug!("no release so required files are not enforced");
+        } else {
+            latest_version.has_required(required, args.enforce_level.clone())?;
+        }
+    }
+    latest_version.check_regression(&answer)?;
+
+    if args.explain {
+        explain_calculation(&latest_version, &answer);
+    }
+
+    Ok((latest_version, answer))
+}
+
+/// Create the tag for the next version once confirmed
+///
+fn tag_version(
+    latest_version: &VersionCalculator,
+    answer: &Answer,
+    yes: bool,
+) -> Result<(), Error> {
+    if answer.bump_level == Level::None {
+        log::info!("no change to release so no tag is created");
+        return Ok(());
+    }
+    let tag_name = latest_version.new_tag_name(&answer.version_number)?;
+    if !yes && !confirm(&format!("Create tag {}?", tag_name))? {
+        return Err(Error::TagNotConfirmed(tag_name));
+    }
+    latest_version.create_tag(&answer.version_number)?;
+    Ok(())
+}
+
+/// Ask the question on stderr and read the answer from stdin
+///
+/// Defaults to no, including when stdin is not a terminal.
+///
+fn confirm(question: &str) -> Result<bool, Error> {
+    if !std::io::stdin().is_terminal() {
+        log::warn!("stdin is not a terminal so the answer is no");
+        return Ok(false);
+    }
+    eprint!("{} [y/N] ", question);
+    std::io::stderr().flush()?;
+    let mut reply = String::new();
+    std::io::stdin().read_line(&mut reply)?;
+    Ok(matches!(reply.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// Explain the calculation on stderr
+///
+fn explain_calculation(latest_version: &VersionCalculator, answer: &Answer) {
+    let oid = match latest_version.baseline_oid() {
+        Some(oid) => oid.to_string(),
+        None => "unresolved".to_string(),
+    };
+    eprintln!("baseline tag: refs/tags/{} ({})", answer.baseline_tag, oid);
+    eprintln!("change level: {:?}", answer.change_level());
+    eprintln!("bump level: {}", answer.bump_level);
+    eprintln!("next version: {}", answer.version_number);
 }
 
 pub fn get_logging(level: log::LevelFilter) -> env_logger::Builder {
@@ -159,11 +1071,46 @@ pub fn get_logging(level: log::LevelFilter) -> env_logger::Builder {
 
 /// Print the output from the calculation
 ///
-fn print_output(number: bool, level: bool, response: Answer) {
-    match (number, level) {
-        (false, false) => println!("{}", response.bump_level),
-        (false, true) => println!("{}", response.bump_level),
-        (true, false) => println!("{}", response.version_number),
-        (true, true) => println!("{}\n{}", response.version_number, response.bump_level),
+fn print_output(
+    args: &Cli,
+    output: Output,
+    response: &Answer,
+    build: Option<&str>,
+    report: Option<String>,
+) -> Result<(), Error> {
+    let mut version = response.version_number.clone();
+    if let Some(build) = build {
+        version.set_build(build)?;
+    }
+    let mut version = version.to_string();
+    if args.docker_tag {
+        version = docker_tag(&version);
     }
+    let mut content = match output {
+        Output::Version => version,
+        Output::Level => response.bump_level.to_string(),
+        Output::Both => format!("version: {}\nlevel: {}", version, response.bump_level),
+        Output::Describe | Output::Changelog | Output::Previews | Output::Jsonl => {
+            report.unwrap_or(version)
+        }
+    };
+    if !args.no_newline {
+        content.push('\n');
+    }
+    match &args.output_file {
+        Some(path) => {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(path, content)?;
+            log::debug!("output written to {:?}", path);
+        }
+        None => {
+            let stdout = std::io::stdout();
+            let mut stdout = stdout.lock();
+            stdout.write_all(content.as_bytes())?;
+            stdout.flush()?;
+        }
+    }
+    Ok(())
 }
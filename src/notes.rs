@@ -0,0 +1,99 @@
+//! Release directives read from git notes
+//!
+//! A note attached to a commit can override how the commit is counted.
+//! Directives are given one per line in the form `key: value`:
+//!
+//! - `bump: major|minor|patch` - count the commit at the level given
+//! - `skip: true` - ignore the commit when calculating the next version
+//!
+
+use crate::ForceLevel;
+
+/// The directives found in a note
+///
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct Directives {
+    pub(crate) bump: Option<ForceLevel>,
+    pub(crate) skip: bool,
+}
+
+impl Directives {
+    /// Read the note for the commit from notes ref and parse the directives
+    ///
+    /// If no note is found no directives are returned.
+    ///
+    pub(crate) fn read(repo: &git2::Repository, notes_ref: &str, id: git2::Oid) -> Directives {
+        match repo.find_note(Some(notes_ref), id) {
+            Ok(note) => Directives::parse(note.message().unwrap_or_default()),
+            Err(_) => Directives::default(),
+        }
+    }
+
+    /// Parse the directives in the text of a note
+    ///
+    /// Unrecognised lines are ignored.
+    ///
+    pub(crate) fn parse(note: &str) -> Directives {
+        let mut directives = Directives::default();
+        for line in note.lines() {
+            let (key, value) = match line.split_once(':') {
+                Some(pair) => pair,
+                None => continue,
+            };
+            let value = value.trim().to_lowercase();
+            match key.trim().to_lowercase().as_str() {
+                "bump" => {
+                    directives.bump = match value.as_str() {
+                        "major" => Some(ForceLevel::Major),
+                        "minor" => Some(ForceLevel::Minor),
+                        "patch" => Some(ForceLevel::Patch),
+                        _ => {
+                            log::warn!("note directive bump has unknown level: {}", value);
+                            directives.bump
+                        }
+                    }
+                }
+                "skip" => directives.skip = value == "true",
+                _ => log::trace!("note line ignored: {}", line),
+            }
+        }
+        log::trace!("note directives: {:?}", &directives);
+        directives
+    }
+}
+
+/// Expand a short notes ref (e.g. `nextsv`) to the full ref name
+///
+pub(crate) fn full_ref(notes_ref: &str) -> String {
+    if notes_ref.starts_with("refs/") {
+        notes_ref.to_string()
+    } else {
+        format!("refs/notes/{}", notes_ref)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_bump_and_skip() {
+        let directives = Directives::parse("bump: Major\nskip: true\n");
+
+        assert_eq!(Some(ForceLevel::Major), directives.bump);
+        assert!(directives.skip);
+    }
+
+    #[test]
+    fn parse_ignores_unknown_lines() {
+        let directives = Directives::parse("reviewed by someone\nbump: huge\n");
+
+        assert_eq!(Directives::default(), directives);
+    }
+
+    #[test]
+    fn expand_short_notes_ref() {
+        assert_eq!("refs/notes/nextsv", full_ref("nextsv"));
+        assert_eq!("refs/notes/nextsv", full_ref("refs/notes/nextsv"));
+    }
+}
@@ -0,0 +1,69 @@
+//! The full picture of the next release
+//!
+//! `VersionCalculator::plan` walks the commits and reports the next
+//! version, the changelog, the required files check and the counts of
+//! the commits in a single `ReleasePlan`.
+//!
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    ffi::OsString,
+};
+
+use crate::{Changelog, Level, Semantic, TypeHierarchy};
+
+/// The options for building a release plan
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlanOptions {
+    /// start or continue a pre-release series with the label
+    pub pre_release: Option<String>,
+    /// the files required in the release
+    pub required: Vec<OsString>,
+    /// the level at which the required files are enforced
+    pub enforce_level: TypeHierarchy,
+}
+
+impl Default for PlanOptions {
+    fn default() -> Self {
+        PlanOptions {
+            pre_release: None,
+            required: vec![],
+            enforce_level: TypeHierarchy::Feature,
+        }
+    }
+}
+
+/// The counts of the commits since the baseline tag
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlanMetrics {
+    /// the number of commits walked
+    pub commits: usize,
+    /// the number of breaking changes
+    pub breaking: u32,
+    /// the number of commits of each conventional type
+    pub types: BTreeMap<String, u32>,
+    /// the number of commits of each author (if author stats are set)
+    pub authors: HashMap<String, u32>,
+}
+
+/// The plan for the next release
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReleasePlan {
+    /// the current version
+    pub previous: Semantic,
+    /// the next version
+    pub next: Semantic,
+    /// the level of the change from the previous version
+    pub level: Level,
+    /// the changelog for the next version
+    pub changelog: Changelog,
+    /// the name of the tag for the next version
+    pub tag_name: String,
+    /// the required files are in the release (or not enforced)
+    pub required_ok: bool,
+    /// the counts of the commits
+    pub metrics: PlanMetrics,
+}
@@ -5,11 +5,13 @@
 //!
 //! ## Notes
 //!
-//! Initial implementation does not include support
-//! for pre-release suffixes.
+//! Pre-release suffixes are parsed and ordered as defined by the
+//! specification. Build metadata is not supported.
 //!
 
-use std::fmt;
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+use clap::ValueEnum;
 
 use crate::Error;
 
@@ -41,6 +43,39 @@ impl Default for Level {
     }
 }
 
+impl Level {
+    /// Report if the level is a breaking release (major)
+    ///
+    /// Before 1.0.0 a breaking change is released at a lower level, so
+    /// use `is_breaking_from` with the current version or
+    /// `Answer::is_breaking_change` to take account of it.
+    ///
+    pub fn is_breaking(&self) -> bool {
+        *self == Level::Major
+    }
+
+    /// Report if the level is a breaking release bumped from the version
+    ///
+    /// Before 1.0.0 a bump of the minor number is treated as breaking, as
+    /// cargo does, so a minor level is breaking from `v0.4.2`.
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), nextsv::Error> {
+    /// use nextsv::{Level, Semantic};
+    ///
+    /// assert!(Level::Minor.is_breaking_from(&Semantic::parse("v0.4.2", "v")?));
+    /// assert!(!Level::Minor.is_breaking_from(&Semantic::parse("v1.4.2", "v")?));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn is_breaking_from(&self, current: &Semantic) -> bool {
+        match self {
+            Level::Minor => current.major() == 0,
+            level => level.is_breaking(),
+        }
+    }
+}
+
 impl fmt::Display for Level {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -56,16 +91,302 @@ impl fmt::Display for Level {
     }
 }
 
+/// An identifier in a pre-release suffix
+///
+/// Numeric identifiers have lower precedence than alphanumeric identifiers.
+/// Numeric identifiers are compared numerically and alphanumeric identifiers
+/// are compared lexically in ASCII sort order.
+///
+#[derive(Debug, PartialEq, PartialOrd, Eq, Ord, Clone)]
+pub enum Identifier {
+    /// An identifier made up of digits only
+    Numeric(usize),
+    /// An identifier including letters or hyphens
+    AlphaNumeric(String),
+}
+
+impl fmt::Display for Identifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Identifier::Numeric(n) => write!(f, "{}", n),
+            Identifier::AlphaNumeric(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl Identifier {
+    fn parse(identifier: &str) -> Result<Self, Error> {
+        if identifier.is_empty()
+            || !identifier
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-')
+        {
+            return Err(Error::InvalidPreRelease(identifier.to_string()));
+        }
+        if identifier.chars().all(|c| c.is_ascii_digit()) {
+            // numeric identifiers must not include leading zeroes
+            if identifier.len() > 1 && identifier.starts_with('0') {
+                return Err(Error::InvalidPreRelease(identifier.to_string()));
+            }
+            match identifier.parse::<usize>() {
+                Ok(n) => Ok(Identifier::Numeric(n)),
+                Err(_) => Err(Error::InvalidPreRelease(identifier.to_string())),
+            }
+        } else {
+            Ok(Identifier::AlphaNumeric(identifier.to_string()))
+        }
+    }
+}
+
+impl FromStr for Level {
+    type Err = Error;
+
+    /// Parse the name of a level as displayed e.g. "minor"
+    ///
+    /// Surrounding whitespace and case are ignored.
+    ///
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.trim().to_lowercase().as_str() {
+            "none" => Level::None,
+            "patch" => Level::Patch,
+            "minor" => Level::Minor,
+            "major" => Level::Major,
+            "release" => Level::Release,
+            "alpha" => Level::Alpha,
+            "beta" => Level::Beta,
+            "rc" => Level::Rc,
+            _ => return Err(Error::NotLevelName(s.trim().to_string())),
+        })
+    }
+}
+
+/// The longest tag allowed for a docker image
+const DOCKER_TAG_MAX_LEN: usize = 128;
+
+/// Convert a version string to a valid docker image tag
+///
+/// Docker tags may contain ASCII letters, digits, `_`, `.` and `-`, must
+/// not start with `.` or `-` and are at most 128 characters long. The
+/// version is converted as follows:
+///
+/// - any other character (e.g. `+` or `/`) is replaced with `-`
+/// - leading `.` and `-` characters are removed
+/// - the tag is truncated to 128 characters
+///
+/// ## Example
+///
+/// ```rust
+/// assert_eq!("v1.3.0-rc.1-1a2b3c4", nextsv::docker_tag("v1.3.0-rc.1+1a2b3c4"));
+/// ```
+///
+pub fn docker_tag(version: &str) -> String {
+    version
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '.' || c == '-' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .skip_while(|c| *c == '.' || *c == '-')
+        .take(DOCKER_TAG_MAX_LEN)
+        .collect()
+}
+
+/// The style used to write a pre-release suffix in a version tag
+///
+/// Versions are ordered following the semantic version specification
+/// whatever the style.
+///
+#[derive(Debug, PartialEq, Eq, Clone, Copy, ValueEnum)]
+pub enum PreReleaseStyle {
+    /// hyphen and dot separated identifiers e.g. `v1.2.3-rc.1` (the default)
+    SemVer,
+    /// dot and label with counter e.g. `v1.2.3.rc1`
+    DotSuffix,
+    /// label with counter after the patch number e.g. `v1.2.3rc1`
+    Concatenated,
+}
+
+impl Default for PreReleaseStyle {
+    fn default() -> Self {
+        PreReleaseStyle::SemVer
+    }
+}
+
+/// The pre-release suffix of a semantic version number
+///
+/// A pre-release is a series of dot separated identifiers (e.g. `alpha.1`).
+/// Pre-releases are compared identifier by identifier and a shorter series
+/// has lower precedence if all of the preceding identifiers are equal.
+///
+#[derive(Debug, PartialEq, PartialOrd, Eq, Ord, Clone)]
+pub struct PreRelease {
+    identifiers: Vec<Identifier>,
+}
+
+impl fmt::Display for PreRelease {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let identifiers: Vec<String> = self.identifiers.iter().map(|i| i.to_string()).collect();
+        write!(f, "{}", identifiers.join("."))
+    }
+}
+
+impl PreRelease {
+    /// Parse a pre-release suffix (without the leading hyphen)
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), nextsv::Error> {
+    /// use nextsv::PreRelease;
+    ///
+    /// let pre_release = PreRelease::parse("alpha.1")?;
+    ///
+    /// assert_eq!("alpha.1", pre_release.to_string());
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn parse(pre_release: &str) -> Result<Self, Error> {
+        let mut identifiers = vec![];
+        for identifier in pre_release.split('.') {
+            identifiers.push(Identifier::parse(identifier)?);
+        }
+        Ok(PreRelease { identifiers })
+    }
+
+    /// Parse a pre-release suffix written as labels and counters
+    /// without separators (e.g. `rc1`)
+    ///
+    /// The label and the counter are parsed as separate identifiers, so
+    /// `rc1` is equivalent to `rc.1`. Further identifiers are separated by
+    /// dots (e.g. `alpha1.2` is equivalent to `alpha.1.2`). The pre-release
+    /// must start with a label.
+    ///
+    pub fn parse_compact(pre_release: &str) -> Result<Self, Error> {
+        let mut identifiers = vec![];
+        for part in pre_release.split('.') {
+            let label = part.trim_end_matches(|c: char| c.is_ascii_digit());
+            let counter = &part[label.len()..];
+            if label.is_empty() && (identifiers.is_empty() || counter.is_empty()) {
+                return Err(Error::InvalidPreRelease(pre_release.to_string()));
+            }
+            if !label.is_empty() {
+                identifiers.push(Identifier::parse(label)?);
+            }
+            if !counter.is_empty() {
+                identifiers.push(Identifier::parse(counter)?);
+            }
+        }
+        Ok(PreRelease { identifiers })
+    }
+
+    /// Write the identifiers as read by `parse_compact` (e.g. `rc1` or
+    /// `alpha1.2`)
+    ///
+    /// A counter follows its label without a separator and the other
+    /// identifiers are separated by dots. A label ending in a digit is
+    /// read back as a label and a counter.
+    ///
+    pub fn compact(&self) -> String {
+        let mut compact = String::new();
+        let mut after_label = false;
+        for identifier in &self.identifiers {
+            let counter = after_label && matches!(identifier, Identifier::Numeric(_));
+            if !compact.is_empty() && !counter {
+                compact.push('.');
+            }
+            compact.push_str(&identifier.to_string());
+            after_label = matches!(
+                identifier,
+                Identifier::AlphaNumeric(label) if !label.ends_with(|c: char| c.is_ascii_digit())
+            );
+        }
+        compact
+    }
+
+    /// Report the identifiers in the pre-release
+    ///
+    pub fn identifiers(&self) -> &[Identifier] {
+        &self.identifiers
+    }
+
+    /// Increment the last numeric identifier in the pre-release by 1
+    ///
+    /// Only the last numeric identifier is changed (e.g. `alpha.1.9`
+    /// becomes `alpha.1.10`). If there is no numeric identifier a
+    /// numeric identifier of 1 is added (e.g. `alpha` becomes `alpha.1`).
+    ///
+    pub fn increment(&mut self) -> &mut Self {
+        let last_numeric = self
+            .identifiers
+            .iter_mut()
+            .rev()
+            .find(|i| matches!(i, Identifier::Numeric(_)));
+        match last_numeric {
+            Some(Identifier::Numeric(n)) => *n += 1,
+            _ => self.identifiers.push(Identifier::Numeric(1)),
+        }
+        self
+    }
+
+    /// Reset the last numeric identifier in the pre-release to 1
+    ///
+    /// Only the last numeric identifier is changed (e.g. `rc.5` becomes
+    /// `rc.1`). If there is no numeric identifier a numeric identifier of
+    /// 1 is added (e.g. `alpha` becomes `alpha.1`).
+    ///
+    pub fn reset(&mut self) -> &mut Self {
+        let last_numeric = self
+            .identifiers
+            .iter_mut()
+            .rev()
+            .find(|i| matches!(i, Identifier::Numeric(_)));
+        match last_numeric {
+            Some(Identifier::Numeric(n)) => *n = 1,
+            _ => self.identifiers.push(Identifier::Numeric(1)),
+        }
+        self
+    }
+}
+
+/// The components read from a tag that is not a valid version
+///
+/// Reported by `Semantic::parse_diagnostic` with the issues found. A
+/// component is None if it is missing or could not be read.
+///
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PartialSemantic {
+    /// the version prefix found at the start of the tag
+    pub version_prefix: Option<String>,
+    /// the major number
+    pub major: Option<usize>,
+    /// the minor number
+    pub minor: Option<usize>,
+    /// the patch number
+    pub patch: Option<usize>,
+    /// the pre-release suffix
+    pub pre_release: Option<PreRelease>,
+}
+
 /// The Semantic data structure represents a semantic version number.
 ///
-/// TODO: Implement support for pre-release and build
+/// Versions are ordered by precedence as defined by the semantic version
+/// specification: the major, minor and patch numbers are compared
+/// numerically and a pre-release version has lower precedence than the
+/// associated normal version. Build metadata (e.g. `+1a2b3c4`) is
+/// written after the version and is not part of the precedence.
 ///
-#[derive(Debug, Default, PartialEq, PartialOrd, Eq, Ord, Clone)]
+#[derive(Debug, Default, Clone)]
 pub struct Semantic {
     version_prefix: String,
     major: usize,
     minor: usize,
     patch: usize,
+    pre_release: Option<PreRelease>,
+    build: Option<String>,
+    style: PreReleaseStyle,
+    original_tag: Option<String>,
 }
 
 impl fmt::Display for Semantic {
@@ -74,7 +395,54 @@ impl fmt::Display for Semantic {
             f,
             "{}{}.{}.{}",
             self.version_prefix, self.major, self.minor, self.patch
-        )
+        )?;
+        if let Some(pre_release) = &self.pre_release {
+            match self.style {
+                PreReleaseStyle::SemVer => write!(f, "-{}", pre_release)?,
+                PreReleaseStyle::DotSuffix => write!(f, ".{}", pre_release.compact())?,
+                PreReleaseStyle::Concatenated => write!(f, "{}", pre_release.compact())?,
+            }
+        }
+        if let Some(build) = &self.build {
+            write!(f, "+{}", build)?;
+        }
+        Ok(())
+    }
+}
+
+// The style is how the version is written so it is not compared
+impl PartialEq for Semantic {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Semantic {}
+
+/// Versions are ordered by precedence as defined by the specification
+///
+/// The version prefix, the pre-release style and the build metadata are
+/// not part of the version so `v1.0.0` and `release-1.0.0` are equal.
+///
+impl Ord for Semantic {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.major
+            .cmp(&other.major)
+            .then(self.minor.cmp(&other.minor))
+            .then(self.patch.cmp(&other.patch))
+            .then_with(|| match (&self.pre_release, &other.pre_release) {
+                (None, None) => Ordering::Equal,
+                // a pre-release has lower precedence than a normal version
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(a), Some(b)) => a.cmp(b),
+            })
+    }
+}
+
+impl PartialOrd for Semantic {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
 
@@ -86,16 +454,26 @@ impl Semantic {
             major,
             minor,
             patch,
+            pre_release: None,
+            build: None,
+            style: PreReleaseStyle::SemVer,
+            original_tag: None,
         }
     }
     /// Parse a tag and return a struct
-    /// String format expect: <version_prefix>x.y.z
+    /// String format expect: <version_prefix>x.y.z[-pre-release]
     ///
     /// # Fields
     ///
     /// tag - the tag proposed as a semantic version tag
     /// version_prefix - any string before the semantic version number
     ///
+    /// Exactly one copy of the prefix is removed, so with the prefix "v"
+    /// the tag `vv1.2.3` leaves `v1.2.3`, which is not a number and is
+    /// reported as `Error::MustBeNumber`. A number with a leading zero
+    /// (e.g. `v1.02.3`) is not a semantic version and is reported as
+    /// `Error::LeadingZero`.
+    ///
     /// # Example
     ///
     /// Parse a tag into a semantic version number where "v" is used to identify
@@ -118,15 +496,141 @@ impl Semantic {
     /// to identify tags with semantic version numbers
     /// the tag name can be parsed
     pub fn parse(tag: &str, version_prefix: &str) -> Result<Self, Error> {
-        // the tag string must start with the version_prefix
-        if !tag.starts_with(version_prefix) {
-            return Err(Error::NotVersionTag(
-                version_prefix.to_string(),
-                tag.to_string(),
-            ));
+        Semantic::parse_with_style(tag, version_prefix, PreReleaseStyle::SemVer)
+    }
+
+    /// Parse a tag reporting every issue found and the components read
+    ///
+    /// Unlike `parse` the parse does not stop at the first issue, so
+    /// tools can report all of the problems with a tag. The issues are
+    /// a missing prefix, too many or too few components, components that
+    /// are not numbers or have a leading zero and an invalid pre-release.
+    /// The pre-release suffix is parsed in the semantic version style.
+    ///
+    /// Returns None for the components if none could be read.
+    ///
+    /// ```rust
+    /// use nextsv::{Error, Semantic};
+    ///
+    /// let (partial, issues) = Semantic::parse_diagnostic("v1.x", "v");
+    ///
+    /// assert_eq!(Some(1), partial.unwrap().major);
+    /// assert!(matches!(issues[0], Error::MustBeNumber(_)));
+    /// assert!(matches!(issues[1], Error::TooFewComponents(2)));
+    /// ```
+    pub fn parse_diagnostic(
+        tag: &str,
+        version_prefix: &str,
+    ) -> (Option<PartialSemantic>, Vec<Error>) {
+        let mut issues = vec![];
+        let mut partial = PartialSemantic::default();
+
+        let version = match tag.strip_prefix(version_prefix) {
+            Some(version) => {
+                partial.version_prefix = Some(version_prefix.to_string());
+                version
+            }
+            None => {
+                issues.push(Error::NotVersionTag(
+                    version_prefix.to_string(),
+                    tag.to_string(),
+                ));
+                tag
+            }
+        };
+
+        let version = match version.split_once('-') {
+            Some((version, pre_release)) => {
+                match PreRelease::parse(pre_release) {
+                    Ok(pre_release) => partial.pre_release = Some(pre_release),
+                    Err(e) => issues.push(e),
+                }
+                version
+            }
+            None => version,
+        };
+
+        let components: Vec<&str> = version.split('.').collect();
+        let numbers = [&mut partial.major, &mut partial.minor, &mut partial.patch];
+        for (item, number) in components.iter().zip(numbers) {
+            match item.parse::<usize>() {
+                Ok(n) => {
+                    if item.len() > 1 && item.starts_with('0') {
+                        issues.push(Error::LeadingZero(item.to_string()));
+                    }
+                    *number = Some(n);
+                }
+                Err(_) => issues.push(Error::MustBeNumber(item.to_string())),
+            }
+        }
+        match components.len() {
+            3 => {}
+            n if n > 3 => issues.push(Error::TooManyComponents(n)),
+            n => issues.push(Error::TooFewComponents(n)),
         }
 
-        let version = tag.trim_start_matches(version_prefix);
+        let read = partial.major.is_some()
+            || partial.minor.is_some()
+            || partial.patch.is_some()
+            || partial.pre_release.is_some();
+        (if read { Some(partial) } else { None }, issues)
+    }
+
+    /// Parse a tag with the pre-release suffix written in the style given
+    ///
+    /// The style is kept and used when the version is displayed.
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), nextsv::Error> {
+    /// use nextsv::{PreReleaseStyle, Semantic};
+    ///
+    /// let version = Semantic::parse_with_style("v1.2.3.rc1", "v", PreReleaseStyle::DotSuffix)?;
+    ///
+    /// assert_eq!(Semantic::parse("v1.2.3-rc.1", "v")?, version);
+    /// assert_eq!("v1.2.3.rc1", version.to_string());
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn parse_with_style(
+        tag: &str,
+        version_prefix: &str,
+        style: PreReleaseStyle,
+    ) -> Result<Self, Error> {
+        // the tag string must start with the version_prefix and exactly
+        // one copy of the prefix is removed (the prefix may end in digits)
+        let version = match tag.strip_prefix(version_prefix) {
+            Some(version) => version,
+            None => {
+                return Err(Error::NotVersionTag(
+                    version_prefix.to_string(),
+                    tag.to_string(),
+                ))
+            }
+        };
+        let (version, pre_release) = match style {
+            PreReleaseStyle::SemVer => match version.split_once('-') {
+                Some((version, pre_release)) => (version, Some(PreRelease::parse(pre_release)?)),
+                None => (version, None),
+            },
+            PreReleaseStyle::DotSuffix => match version.splitn(4, '.').nth(3) {
+                Some(pre_release) => (
+                    &version[..version.len() - pre_release.len() - 1],
+                    Some(PreRelease::parse_compact(pre_release)?),
+                ),
+                None => (version, None),
+            },
+            PreReleaseStyle::Concatenated => {
+                // the pre-release starts at the first character after the numbers
+                match version.find(|c: char| !c.is_ascii_digit() && c != '.') {
+                    Some(i) => (
+                        &version[..i],
+                        Some(PreRelease::parse_compact(&version[i..])?),
+                    ),
+                    None => (version, None),
+                }
+            }
+        };
         let components: Vec<&str> = version.split('.').collect();
 
         let mut count_numbers = 0;
@@ -138,6 +642,9 @@ impl Semantic {
                 return Err(Error::TooManyComponents(count_numbers));
             }
             numbers.push(match item.parse::<usize>() {
+                Ok(_) if item.len() > 1 && item.starts_with('0') => {
+                    return Err(Error::LeadingZero(item.to_string()))
+                }
                 Ok(n) => n,
                 Err(_) => return Err(Error::MustBeNumber(item.to_string())),
             });
@@ -147,54 +654,230 @@ impl Semantic {
             return Err(Error::TooFewComponents(count_numbers));
         }
 
-        Ok(Semantic::new(
+        let mut semantic = Semantic::new(
             version_prefix.to_string(),
             numbers[0],
             numbers[1],
             numbers[2],
-        ))
+        );
+        semantic.pre_release = pre_release;
+        semantic.style = style;
+        semantic.original_tag = Some(tag.to_string());
+
+        Ok(semantic)
+    }
+
+    /// Sort a list of versions into ascending order of precedence
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), nextsv::Error> {
+    /// use nextsv::Semantic;
+    ///
+    /// let mut versions = vec![
+    ///     Semantic::parse("v1.0.0", "v")?,
+    ///     Semantic::parse("v1.0.0-rc.1", "v")?,
+    ///     Semantic::parse("v0.9.0", "v")?,
+    /// ];
+    /// Semantic::sort(&mut versions);
+    ///
+    /// assert_eq!("v0.9.0", versions[0].to_string());
+    /// assert_eq!("v1.0.0-rc.1", versions[1].to_string());
+    /// assert_eq!("v1.0.0", versions[2].to_string());
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn sort(versions: &mut [Semantic]) {
+        versions.sort();
     }
 
     /// Increment the version based on a breaking change
     /// When the major number is 0 increment the minor
     /// number else increment the major number
     ///
+    /// A pre-release is incremented as for `increment_minor` and
+    /// `increment_major`.
+    ///
     pub fn breaking_increment(&mut self) -> &mut Self {
         if self.major == 0 {
-            self.minor += 1;
-            self.patch = 0;
+            self.increment_minor()
         } else {
-            self.major += 1;
-            self.minor = 0;
-            self.patch = 0;
+            self.increment_major()
         }
-        self
     }
 
     /// Increment the patch component of the version number by 1
     ///
+    /// Any pre-release suffix is removed by the increment functions. A
+    /// pre-release is released at its core version if the core already
+    /// has the increment, so `v1.2.3-rc.1` gives `v1.2.3` for a patch,
+    /// `v1.3.0-rc.1` gives `v1.3.0` for a minor or patch and `v2.0.0-rc.1`
+    /// gives `v2.0.0` for any increment.
+    ///
     pub fn increment_patch(&mut self) -> &mut Self {
-        self.patch += 1;
+        if self.pre_release.is_none() {
+            self.patch += 1;
+        }
+        self.pre_release = None;
+        self.original_tag = None;
+        self.build = None;
         self
     }
 
     /// Increment the minor component of the version number by 1
     ///
     pub fn increment_minor(&mut self) -> &mut Self {
-        self.minor += 1;
+        if self.pre_release.is_none() || self.patch != 0 {
+            self.minor += 1;
+        }
         self.patch = 0;
+        self.pre_release = None;
+        self.original_tag = None;
+        self.build = None;
         self
     }
 
     /// Increment the major component of the version number by 1
     ///
     pub fn increment_major(&mut self) -> &mut Self {
-        self.major += 1;
+        if self.pre_release.is_none() || self.minor != 0 || self.patch != 0 {
+            self.major += 1;
+        }
         self.minor = 0;
         self.patch = 0;
+        self.pre_release = None;
+        self.original_tag = None;
+        self.build = None;
         self
     }
 
+    /// Increment the pre-release suffix of the version number
+    ///
+    /// See `PreRelease::increment` for the rules applied.
+    ///
+    /// ## Error
+    ///
+    /// Report error if the version is not a pre-release.
+    ///
+    pub fn increment_pre_release(&mut self) -> Result<&mut Self, Error> {
+        match self.pre_release.as_mut() {
+            Some(pre_release) => {
+                pre_release.increment();
+            }
+            None => return Err(Error::NotPreRelease(self.to_string())),
+        }
+        self.original_tag = None;
+        self.build = None;
+        Ok(self)
+    }
+
+    /// Reset the counter of the pre-release suffix to 1 (e.g. `v1.2.0-rc.5`
+    /// becomes `v1.2.0-rc.1`)
+    ///
+    /// See `PreRelease::reset` for the rules applied.
+    ///
+    /// ## Error
+    ///
+    /// Report error if the version is not a pre-release.
+    ///
+    pub fn reset_pre_release_counter(&mut self) -> Result<&mut Self, Error> {
+        match self.pre_release.as_mut() {
+            Some(pre_release) => {
+                pre_release.reset();
+            }
+            None => return Err(Error::NotPreRelease(self.to_string())),
+        }
+        self.original_tag = None;
+        self.build = None;
+        Ok(self)
+    }
+
+    /// Start a pre-release series with the label given
+    ///
+    /// The pre-release suffix is set to the label with the counter 1
+    /// (e.g. "rc" gives `-rc.1`). The core version is not changed.
+    ///
+    /// ## Error
+    ///
+    /// Report error if the label is not a valid pre-release identifier.
+    ///
+    pub fn start_pre_release(&mut self, label: &str) -> Result<&mut Self, Error> {
+        self.pre_release = Some(PreRelease::parse(&format!("{}.1", label))?);
+        self.original_tag = None;
+        self.build = None;
+        Ok(self)
+    }
+
+    /// Report the first pre-release of the core version bumped at the level
+    ///
+    /// The core version is bumped as given and the pre-release series for
+    /// the label is started, so `v1.2.0` with `Level::Minor` and "rc" gives
+    /// `v1.3.0-rc.1`. Any current pre-release suffix is replaced.
+    ///
+    /// The level is applied as given. While the major number is 0 the
+    /// `VersionCalculator` reports a breaking change at `Level::Minor` and a
+    /// feature at `Level::Patch`; pass the level it reports to follow them.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), nextsv::Error> {
+    /// use nextsv::{Level, Semantic};
+    ///
+    /// let version = Semantic::parse("v1.2.0", "v")?;
+    ///
+    /// assert_eq!("v1.3.0-rc.1", version.next_pre_release_for(Level::Minor, "rc")?.to_string());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// ## Error
+    ///
+    /// Report error if the level is not major, minor or patch or the label
+    /// is not a valid pre-release identifier.
+    ///
+    pub fn next_pre_release_for(&self, level: Level, label: &str) -> Result<Semantic, Error> {
+        let mut version = self.truncate_to(Level::Patch);
+        match level {
+            Level::Major => version.increment_major(),
+            Level::Minor => version.increment_minor(),
+            Level::Patch => version.increment_patch(),
+            _ => return Err(Error::NotCoreLevel(level.to_string())),
+        };
+        version.start_pre_release(label)?;
+        Ok(version)
+    }
+
+    /// Report the development version following the release
+    ///
+    /// The minor number is bumped and the pre-release for the stage is
+    /// started at 0, marking the work towards the next release, so that
+    /// `v1.2.0` with the stage "dev" gives `v1.3.0-dev.0`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), nextsv::Error> {
+    /// use nextsv::Semantic;
+    ///
+    /// let release = Semantic::parse("v1.2.0", "v")?;
+    ///
+    /// assert_eq!("v1.3.0-dev.0", release.post_release_dev("dev")?.to_string());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// ## Error
+    ///
+    /// Report error if the stage is not a valid pre-release identifier.
+    ///
+    pub fn post_release_dev(&self, stage: &str) -> Result<Semantic, Error> {
+        let mut version = self.truncate_to(Level::Patch);
+        version.increment_minor();
+        version.pre_release = Some(PreRelease::parse(&format!("{}.0", stage))?);
+        Ok(version)
+    }
+
     /// Set the first production release version
     ///
     pub fn first_production(&mut self) -> Result<&mut Self, Error> {
@@ -204,16 +887,110 @@ impl Semantic {
             self.major = 1;
             self.minor = 0;
             self.patch = 0;
+            self.pre_release = None;
+            self.original_tag = None;
+            self.build = None;
         }
         Ok(self)
     }
 
-    /// Report the major version number
+    /// Truncate the version to the level given
     ///
-    pub fn major(&self) -> usize {
-        self.major
-    }
-    /// Report the minor version number
+    /// The components below the level are set to zero and any pre-release
+    /// suffix is removed. The prefix is kept. `Level::None` and the
+    /// pre-release levels return the version unchanged.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), nextsv::Error> {
+    /// use nextsv::{Level, Semantic};
+    ///
+    /// let version = Semantic::parse("v1.2.3", "v")?;
+    ///
+    /// assert_eq!("v1.2.0", version.truncate_to(Level::Minor).to_string());
+    /// assert_eq!("v1.0.0", version.truncate_to(Level::Major).to_string());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn truncate_to(&self, level: Level) -> Semantic {
+        let mut truncated = self.clone();
+        match level {
+            Level::Major => {
+                truncated.minor = 0;
+                truncated.patch = 0;
+            }
+            Level::Minor => truncated.patch = 0,
+            Level::Patch => {}
+            _ => return truncated,
+        }
+        truncated.pre_release = None;
+        truncated.original_tag = None;
+        truncated.build = None;
+        truncated
+    }
+
+    /// Report the name of the tag for the version
+    ///
+    /// The tag name is the prefix, the version number and any pre-release
+    /// suffix written in the pre-release style (e.g. `v1.2.3-rc.1`). Build
+    /// metadata is not supported so is never included in the tag name.
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), nextsv::Error> {
+    /// use nextsv::Semantic;
+    ///
+    /// let version = Semantic::parse("v1.2.3-rc.1", "v")?;
+    ///
+    /// assert_eq!("v1.2.3-rc.1", version.tag_name());
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn tag_name(&self) -> String {
+        self.to_string()
+    }
+
+    /// Report the style used to write the pre-release suffix
+    ///
+    pub fn pre_release_style(&self) -> PreReleaseStyle {
+        self.style
+    }
+
+    /// Set the style used to write the pre-release suffix
+    ///
+    pub fn set_pre_release_style(&mut self, style: PreReleaseStyle) -> &mut Self {
+        self.style = style;
+        self.original_tag = None;
+        self
+    }
+
+    /// Report the version prefix
+    ///
+    pub fn version_prefix(&self) -> &str {
+        &self.version_prefix
+    }
+
+    /// Remove the version prefix so the version is a bare number (e.g. 1.2.3)
+    ///
+    pub fn strip_version_prefix(&mut self) -> &mut Self {
+        self.set_version_prefix("")
+    }
+
+    /// Replace the version prefix (e.g. to report `v1.2.3` for the tag `1.2.3`)
+    ///
+    pub fn set_version_prefix(&mut self, version_prefix: &str) -> &mut Self {
+        self.version_prefix = version_prefix.to_string();
+        self.original_tag = None;
+        self
+    }
+
+    /// Report the major version number
+    ///
+    pub fn major(&self) -> usize {
+        self.major
+    }
+    /// Report the minor version number
     pub fn minor(&self) -> usize {
         self.minor
     }
@@ -223,12 +1000,204 @@ impl Semantic {
     pub fn patch(&self) -> usize {
         self.patch
     }
+
+    /// Report the pre-release suffix
+    ///
+    pub fn pre_release(&self) -> Option<&PreRelease> {
+        self.pre_release.as_ref()
+    }
+
+    /// Report if the version is a pre-release
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), nextsv::Error> {
+    /// use nextsv::Semantic;
+    ///
+    /// assert!(Semantic::parse("v1.2.0-rc.1", "v")?.is_pre_release());
+    /// assert!(!Semantic::parse("v1.2.0", "v")?.is_pre_release());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn is_pre_release(&self) -> bool {
+        self.pre_release.is_some()
+    }
+
+    /// Set the pre-release suffix replacing any current suffix
+    ///
+    pub fn set_pre_release(&mut self, pre_release: PreRelease) -> &mut Self {
+        self.pre_release = Some(pre_release);
+        self.original_tag = None;
+        self.build = None;
+        self
+    }
+
+    /// Report the build metadata
+    ///
+    pub fn build(&self) -> Option<&str> {
+        self.build.as_deref()
+    }
+
+    /// Set the build metadata written after the version (e.g. the commit
+    /// id `1a2b3c4` in `v1.3.0+1a2b3c4`)
+    ///
+    /// The build metadata is not part of the precedence and is removed when
+    /// the version is changed.
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), nextsv::Error> {
+    /// use nextsv::Semantic;
+    ///
+    /// let mut version = Semantic::parse("v1.3.0", "v")?;
+    /// version.set_build("1a2b3c4")?;
+    ///
+    /// assert_eq!("v1.3.0+1a2b3c4", version.to_string());
+    /// assert_eq!(Semantic::parse("v1.3.0", "v")?, version);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// ## Error
+    ///
+    /// Report `Error::InvalidBuild` if an identifier is empty or has a
+    /// character other than ASCII letters, digits and hyphens.
+    ///
+    pub fn set_build(&mut self, build: &str) -> Result<&mut Self, Error> {
+        for identifier in build.split('.') {
+            if identifier.is_empty()
+                || !identifier
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-')
+            {
+                return Err(Error::InvalidBuild(identifier.to_string()));
+            }
+        }
+        self.build = Some(build.to_string());
+        self.original_tag = None;
+        Ok(self)
+    }
+
+    /// Report the tag the version was parsed from, exactly as written
+    ///
+    /// The tag is only kept while the version is unchanged, so a version
+    /// bumped from a parsed version reports None. `Display` is not
+    /// affected and writes the version in the canonical form.
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), nextsv::Error> {
+    /// use nextsv::Semantic;
+    ///
+    /// let mut version = Semantic::parse("v1.2.3", "v")?;
+    /// assert_eq!(Some("v1.2.3"), version.original_tag());
+    ///
+    /// version.increment_patch();
+    /// assert_eq!(None, version.original_tag());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn original_tag(&self) -> Option<&str> {
+        self.original_tag.as_deref()
+    }
+
+    /// Record the full name of the tag the version was parsed from
+    ///
+    /// Used when the version was parsed from part of the tag name (e.g.
+    /// without the suffix of a tag glob).
+    ///
+    pub(crate) fn with_original_tag(mut self, tag: &str) -> Self {
+        self.original_tag = Some(tag.to_string());
+        self
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn only_major_is_breaking() {
+        let initial = Semantic::parse("v0.4.2", "v").unwrap();
+        let stable = Semantic::parse("v1.4.2", "v").unwrap();
+        let cases = [
+            (Level::None, false, false),
+            (Level::Patch, false, false),
+            (Level::Minor, false, true),
+            (Level::Major, true, true),
+            (Level::Rc, false, false),
+        ];
+        for (level, breaking, breaking_initial) in cases {
+            assert_eq!(breaking, level.is_breaking(), "{}", level);
+            assert_eq!(breaking, level.is_breaking_from(&stable), "{}", level);
+            assert_eq!(
+                breaking_initial,
+                level.is_breaking_from(&initial),
+                "{}",
+                level
+            );
+        }
+    }
+
+    #[test]
+    fn next_pre_release_for_bumps_the_core_at_each_level() {
+        let cases = [
+            ("v1.2.0", Level::Major, "v2.0.0-beta.1"),
+            ("v1.2.0", Level::Minor, "v1.3.0-beta.1"),
+            ("v1.2.0", Level::Patch, "v1.2.1-beta.1"),
+            ("v1.2.0-alpha.3", Level::Minor, "v1.3.0-beta.1"),
+            ("v0.4.2", Level::Major, "v1.0.0-beta.1"),
+            ("v0.4.2", Level::Minor, "v0.5.0-beta.1"),
+            ("v0.4.2", Level::Patch, "v0.4.3-beta.1"),
+        ];
+        for (version, level, expected) in cases {
+            let version = Semantic::parse(version, "v").unwrap();
+
+            let next = version.next_pre_release_for(level.clone(), "beta").unwrap();
+
+            assert_eq!(expected, next.to_string(), "{} {}", version, level);
+        }
+        let version = Semantic::parse("v1.2.0", "v").unwrap();
+        assert!(matches!(
+            version.next_pre_release_for(Level::None, "beta"),
+            Err(Error::NotCoreLevel(_))
+        ));
+    }
+
+    #[test]
+    fn docker_tag_sanitizes_the_version() {
+        let cases = [
+            ("v1.3.0", "v1.3.0"),
+            ("v1.3.0+1a2b3c4", "v1.3.0-1a2b3c4"),
+            ("release/1.3.0", "release-1.3.0"),
+            ("-1.3.0", "1.3.0"),
+            (".v1.3.0+büild", "v1.3.0-b-ild"),
+        ];
+        for (version, expected) in cases {
+            assert_eq!(expected, docker_tag(version), "{}", version);
+        }
+        assert_eq!(128, docker_tag(&"1".repeat(200)).len());
+    }
+
+    #[test]
+    fn level_parses_from_its_name() {
+        let levels = [
+            Level::None,
+            Level::Patch,
+            Level::Minor,
+            Level::Major,
+            Level::Release,
+            Level::Alpha,
+            Level::Beta,
+            Level::Rc,
+        ];
+        for level in levels {
+            assert_eq!(level, level.to_string().parse().unwrap());
+        }
+        assert_eq!(Level::Minor, " Minor\n".parse().unwrap());
+        assert!(matches!(
+            "feature".parse::<Level>(),
+            Err(Error::NotLevelName(_))
+        ));
+    }
+
     #[test]
     fn display_semantic_version_number() {
         let version = Semantic::default();
@@ -260,6 +1229,228 @@ mod tests {
         assert_eq!("1.0.0", &updated_version.to_string());
     }
 
+    #[test]
+    fn increment_releases_the_pre_release_core() {
+        let cases = [
+            ("v1.2.3-rc.1", Level::Patch, "v1.2.3"),
+            ("v1.2.3-rc.1", Level::Minor, "v1.3.0"),
+            ("v1.2.3-rc.1", Level::Major, "v2.0.0"),
+            ("v1.3.0-rc.1", Level::Patch, "v1.3.0"),
+            ("v1.3.0-rc.1", Level::Minor, "v1.3.0"),
+            ("v1.3.0-rc.1", Level::Major, "v2.0.0"),
+            ("v2.0.0-rc.1", Level::Patch, "v2.0.0"),
+            ("v2.0.0-rc.1", Level::Major, "v2.0.0"),
+            ("v1.2.3", Level::Patch, "v1.2.4"),
+        ];
+        for (tag, level, expected) in cases {
+            let mut version = Semantic::parse(tag, "v").unwrap();
+            match level {
+                Level::Major => version.increment_major(),
+                Level::Minor => version.increment_minor(),
+                _ => version.increment_patch(),
+            };
+
+            assert_eq!(expected, version.to_string(), "{} {}", tag, level);
+        }
+    }
+
+    #[test]
+    fn start_pre_release_series() {
+        let mut version = Semantic::parse("v1.3.0", "v").unwrap();
+        version.start_pre_release("rc").unwrap();
+
+        assert_eq!("v1.3.0-rc.1", version.to_string());
+    }
+
+    #[test]
+    fn start_pre_release_error_invalid_label() {
+        let mut version = Semantic::parse("v1.3.0", "v").unwrap();
+
+        assert!(matches!(
+            version.start_pre_release("r c"),
+            Err(Error::InvalidPreRelease(_))
+        ));
+    }
+
+    #[test]
+    fn build_is_written_but_not_compared() {
+        let release = Semantic::parse("v1.3.0", "v").unwrap();
+        let mut version = release.clone();
+        version.set_build("1a2b3c4.dirty").unwrap();
+
+        assert_eq!(Some("1a2b3c4.dirty"), version.build());
+        assert_eq!("v1.3.0+1a2b3c4.dirty", version.to_string());
+        assert_eq!(release, version);
+
+        version.increment_patch();
+        assert_eq!(None, version.build());
+        assert_eq!("v1.3.1", version.to_string());
+    }
+
+    #[test]
+    fn build_identifiers_are_checked() {
+        let mut version = Semantic::parse("v1.3.0", "v").unwrap();
+        for build in ["", "abc..1", "abc+1", "a b"] {
+            assert!(
+                matches!(version.set_build(build), Err(Error::InvalidBuild(_))),
+                "{}",
+                build
+            );
+        }
+        assert_eq!(None, version.build());
+    }
+
+    #[test]
+    fn pre_release_styles_round_trip() {
+        let cases = [
+            ("v1.2.3-rc.1", PreReleaseStyle::SemVer),
+            ("v1.2.3.rc1", PreReleaseStyle::DotSuffix),
+            ("v1.2.3rc1", PreReleaseStyle::Concatenated),
+        ];
+        let expected = Semantic::parse("v1.2.3-rc.1", "v").unwrap();
+        for (tag, style) in cases {
+            let version = Semantic::parse_with_style(tag, "v", style).unwrap();
+
+            assert_eq!(expected, version, "{}", tag);
+            assert_eq!(tag, version.to_string());
+            assert_eq!(style, version.pre_release_style());
+        }
+    }
+
+    #[test]
+    fn pre_release_styles_round_trip_multiple_identifiers() {
+        let cases = [
+            ("v1.2.3-alpha.1.2", PreReleaseStyle::SemVer),
+            ("v1.2.3.alpha1.2", PreReleaseStyle::DotSuffix),
+            ("v1.2.3alpha1.2", PreReleaseStyle::Concatenated),
+            ("v1.2.3.beta.rc1", PreReleaseStyle::DotSuffix),
+            ("v1.2.3beta.rc1", PreReleaseStyle::Concatenated),
+        ];
+        for (tag, style) in cases {
+            let version = Semantic::parse_with_style(tag, "v", style).unwrap();
+            let reparsed = Semantic::parse_with_style(&version.to_string(), "v", style).unwrap();
+
+            assert_eq!(tag, version.to_string());
+            assert_eq!(version, reparsed, "{}", tag);
+        }
+        let expected = Semantic::parse("v1.2.3-alpha.1.2", "v").unwrap();
+        let mut version = expected.clone();
+        version.set_pre_release_style(PreReleaseStyle::Concatenated);
+        assert_eq!("v1.2.3alpha1.2", version.to_string());
+        let reparsed =
+            Semantic::parse_with_style("v1.2.3alpha1.2", "v", PreReleaseStyle::Concatenated)
+                .unwrap();
+        assert_eq!(expected, reparsed);
+    }
+
+    #[test]
+    fn compact_pre_release_must_start_with_a_label() {
+        for pre_release in ["1", "rc1..2", "rc."] {
+            assert!(
+                PreRelease::parse_compact(pre_release).is_err(),
+                "{}",
+                pre_release
+            );
+        }
+    }
+
+    #[test]
+    fn pre_release_styles_without_pre_release() {
+        for style in [
+            PreReleaseStyle::SemVer,
+            PreReleaseStyle::DotSuffix,
+            PreReleaseStyle::Concatenated,
+        ] {
+            let version = Semantic::parse_with_style("v1.2.3", "v", style).unwrap();
+
+            assert_eq!(None, version.pre_release());
+            assert_eq!("v1.2.3", version.to_string());
+        }
+    }
+
+    #[test]
+    fn pre_release_styles_order_as_semver() {
+        let rc1 =
+            Semantic::parse_with_style("v1.2.3rc1", "v", PreReleaseStyle::Concatenated).unwrap();
+        let rc2 =
+            Semantic::parse_with_style("v1.2.3rc2", "v", PreReleaseStyle::Concatenated).unwrap();
+        let release =
+            Semantic::parse_with_style("v1.2.3", "v", PreReleaseStyle::Concatenated).unwrap();
+
+        assert!(rc1 < rc2);
+        assert!(rc2 < release);
+    }
+
+    #[test]
+    fn start_pre_release_in_dot_suffix_style() {
+        let mut version =
+            Semantic::parse_with_style("v1.3.0", "v", PreReleaseStyle::DotSuffix).unwrap();
+        version.start_pre_release("rc").unwrap();
+
+        assert_eq!("v1.3.0.rc1", version.to_string());
+    }
+
+    #[test]
+    fn tag_name_for_versions() {
+        let cases = [
+            ("v1.2.3", "v", PreReleaseStyle::SemVer),
+            ("v1.2.3-rc.1", "v", PreReleaseStyle::SemVer),
+            ("1.2.3-alpha.1.2", "", PreReleaseStyle::SemVer),
+            ("release-1.2.3.rc1", "release-", PreReleaseStyle::DotSuffix),
+        ];
+        for (tag, prefix, style) in cases {
+            let version = Semantic::parse_with_style(tag, prefix, style).unwrap();
+
+            assert_eq!(tag, version.tag_name());
+        }
+    }
+
+    #[test]
+    fn strip_version_prefix_leaves_the_bare_number() {
+        let mut version = Semantic::parse("release-1.2.3-rc.1", "release-").unwrap();
+
+        version.strip_version_prefix();
+
+        assert_eq!("1.2.3-rc.1", version.to_string());
+        assert_eq!("", version.version_prefix());
+    }
+
+    #[test]
+    fn set_version_prefix_changes_the_display_only() {
+        let mut version = Semantic::parse("1.2.3-rc.1", "").unwrap();
+
+        version.set_version_prefix("v");
+
+        assert_eq!("v1.2.3-rc.1", version.to_string());
+        assert_eq!("v", version.version_prefix());
+        assert_eq!(Semantic::parse("1.2.3-rc.1", "").unwrap(), version);
+    }
+
+    #[test]
+    fn tag_name_excludes_build_metadata() {
+        // build metadata is not supported so cannot reach the tag name
+        assert!(Semantic::parse("v1.2.3+build.5", "v").is_err());
+    }
+
+    #[test]
+    fn truncate_to_each_level() {
+        let version = Semantic::parse("v1.2.3", "v").unwrap();
+
+        assert_eq!("v1.0.0", version.truncate_to(Level::Major).to_string());
+        assert_eq!("v1.2.0", version.truncate_to(Level::Minor).to_string());
+        assert_eq!("v1.2.3", version.truncate_to(Level::Patch).to_string());
+        assert_eq!("v1.2.3", version.truncate_to(Level::None).to_string());
+    }
+
+    #[test]
+    fn truncate_to_drops_pre_release() {
+        let version = Semantic::parse("v1.2.3-rc.1", "v").unwrap();
+
+        assert_eq!("v1.2.0", version.truncate_to(Level::Minor).to_string());
+        assert_eq!("v1.2.3", version.truncate_to(Level::Patch).to_string());
+        assert_eq!("v1.2.3-rc.1", version.truncate_to(Level::None).to_string());
+    }
+
     #[test]
     fn parse_valid_version_tag_to_new_semantic_struct() {
         let tag = "v0.3.90";
@@ -288,6 +1479,36 @@ mod tests {
         assert_eq!(tag, semantic);
     }
 
+    #[test]
+    fn parse_prefix_containing_digits() {
+        let semantic = Semantic::parse("v2-1.2.3-rc.1", "v2-").unwrap();
+
+        assert_eq!("v2-", semantic.version_prefix());
+        assert_eq!(Semantic::parse("v1.2.3-rc.1", "v").unwrap(), semantic);
+        assert_eq!("v2-1.2.3-rc.1", semantic.to_string());
+        assert!(matches!(
+            Semantic::parse("v2-v2-1.2.3", "v2-"),
+            Err(Error::MustBeNumber(_))
+        ));
+    }
+
+    #[test]
+    fn parse_removes_the_prefix_once() {
+        assert!(matches!(
+            Semantic::parse("vv1.2.3", "v"),
+            Err(Error::MustBeNumber(item)) if item == "v1"
+        ));
+        assert!(matches!(
+            Semantic::parse("version1.2.3", "ver"),
+            Err(Error::MustBeNumber(item)) if item == "sion1"
+        ));
+
+        let semantic = Semantic::parse("release-1.2.3", "release-").unwrap();
+
+        assert_eq!("release-", semantic.version_prefix());
+        assert_eq!("release-1.2.3", semantic.to_string());
+    }
+
     #[test]
     fn parse_error_failed_not_version_tag() {
         let tag = "0.3.90";
@@ -341,7 +1562,7 @@ mod tests {
 
     #[test]
     fn parse_error_version_must_be_a_number() {
-        let tag = "v0.3.90-8";
+        let tag = "v0.3.90a8";
         let version_prefix = "v";
         let semantic = Semantic::parse(tag, version_prefix);
 
@@ -350,8 +1571,362 @@ mod tests {
             Ok(s) => s.to_string(),
             Err(e) => e.to_string(),
         };
-        assert_eq!("Version must be a number but found 90-8", semantic);
+        assert_eq!("Version must be a number but found 90a8", semantic);
+    }
+
+    #[test]
+    fn parse_numeric_pre_release() {
+        let tag = "v0.3.90-8";
+        let semantic = Semantic::parse(tag, "v").unwrap();
+
+        assert_eq!(
+            &[Identifier::Numeric(8)],
+            semantic.pre_release().unwrap().identifiers()
+        );
+        assert_eq!(tag, semantic.to_string());
+    }
+
+    #[test]
+    fn parse_pre_release_with_identifiers() {
+        let tag = "v1.0.0-alpha.1";
+        let semantic = Semantic::parse(tag, "v").unwrap();
+
+        assert_eq!(
+            &[
+                Identifier::AlphaNumeric("alpha".to_string()),
+                Identifier::Numeric(1)
+            ],
+            semantic.pre_release().unwrap().identifiers()
+        );
+        assert_eq!(tag, semantic.to_string());
+    }
+
+    #[test]
+    fn parse_error_empty_pre_release_identifier() {
+        let semantic = Semantic::parse("v1.0.0-alpha..1", "v");
+
+        claims::assert_err!(&semantic);
+        assert_eq!(
+            "Pre-release identifier \"\" is not valid",
+            semantic.unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn parse_error_pre_release_leading_zero() {
+        let semantic = Semantic::parse("v1.0.0-rc.01", "v");
+
+        claims::assert_err!(&semantic);
+        assert_eq!(
+            "Pre-release identifier \"01\" is not valid",
+            semantic.unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn parse_error_leading_zero() {
+        for tag in ["v01.2.3", "v1.02.3", "v1.2.03-rc.1"] {
+            let semantic = Semantic::parse(tag, "v");
+
+            assert!(
+                matches!(semantic, Err(Error::LeadingZero(_))),
+                "{}: {:?}",
+                tag,
+                semantic
+            );
+        }
+        assert_eq!(0, Semantic::parse("v0.0.0", "v").unwrap().major());
+    }
+
+    #[test]
+    fn parse_diagnostic_reports_every_issue() {
+        let (partial, issues) = Semantic::parse_diagnostic("v01.x.3", "v");
+
+        let partial = partial.unwrap();
+        assert_eq!(Some("v".to_string()), partial.version_prefix);
+        assert_eq!(Some(1), partial.major);
+        assert_eq!(None, partial.minor);
+        assert_eq!(Some(3), partial.patch);
+        assert_eq!(2, issues.len());
+        assert!(matches!(&issues[0], Error::LeadingZero(n) if n == "01"));
+        assert!(matches!(&issues[1], Error::MustBeNumber(n) if n == "x"));
+    }
+
+    #[test]
+    fn parse_diagnostic_reports_prefix_and_component_count() {
+        let (partial, issues) = Semantic::parse_diagnostic("x1.2.3.4", "v");
+
+        let partial = partial.unwrap();
+        assert_eq!(None, partial.version_prefix);
+        assert_eq!(Some(2), partial.minor);
+        assert_eq!(3, issues.len());
+        assert!(matches!(&issues[0], Error::NotVersionTag(_, _)));
+        assert!(matches!(&issues[1], Error::MustBeNumber(n) if n == "x1"));
+        assert!(matches!(&issues[2], Error::TooManyComponents(4)));
+    }
+
+    #[test]
+    fn parse_diagnostic_of_valid_version_has_no_issues() {
+        let (partial, issues) = Semantic::parse_diagnostic("v1.2.3-rc.1", "v");
+
+        assert!(issues.is_empty());
+        let partial = partial.unwrap();
+        assert_eq!(
+            (Some(1), Some(2), Some(3)),
+            (partial.major, partial.minor, partial.patch)
+        );
+        assert_eq!("rc.1", partial.pre_release.unwrap().to_string());
+    }
+
+    #[test]
+    fn post_release_dev_bumps_the_minor_number() {
+        let cases = [
+            ("v1.2.0", "v1.3.0-dev.0"),
+            ("v1.2.3", "v1.3.0-dev.0"),
+            ("v0.4.1", "v0.5.0-dev.0"),
+            ("v2.0.0-rc.2", "v2.1.0-dev.0"),
+        ];
+        for (release, expected) in cases {
+            let release = Semantic::parse(release, "v").unwrap();
+
+            assert_eq!(
+                expected,
+                release.post_release_dev("dev").unwrap().to_string()
+            );
+        }
+        let release = Semantic::parse("v1.2.0", "v").unwrap();
+        assert!(matches!(
+            release.post_release_dev("dev!"),
+            Err(Error::InvalidPreRelease(_))
+        ));
+    }
+
+    fn sorted(tags: &[&str]) -> Vec<String> {
+        let mut versions: Vec<Semantic> = tags
+            .iter()
+            .map(|tag| Semantic::parse(tag, "v").unwrap())
+            .collect();
+        Semantic::sort(&mut versions);
+        versions.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn sort_core_versions_numerically() {
+        let expected = vec!["v1.0.0", "v1.0.9", "v1.0.10", "v1.2.0", "v1.10.0", "v2.0.0"];
+
+        assert_eq!(
+            expected,
+            sorted(&["v1.10.0", "v2.0.0", "v1.0.10", "v1.0.0", "v1.2.0", "v1.0.9"])
+        );
+    }
+
+    #[test]
+    fn sort_pre_release_precedence_from_specification() {
+        let expected = vec![
+            "v1.0.0-alpha",
+            "v1.0.0-alpha.1",
+            "v1.0.0-alpha.beta",
+            "v1.0.0-beta",
+            "v1.0.0-beta.2",
+            "v1.0.0-beta.11",
+            "v1.0.0-rc.1",
+            "v1.0.0",
+        ];
+
+        assert_eq!(
+            expected,
+            sorted(&[
+                "v1.0.0",
+                "v1.0.0-rc.1",
+                "v1.0.0-beta.11",
+                "v1.0.0-alpha.beta",
+                "v1.0.0-alpha",
+                "v1.0.0-beta",
+                "v1.0.0-alpha.1",
+                "v1.0.0-beta.2",
+            ])
+        );
+    }
+
+    #[test]
+    fn pre_release_is_lower_than_normal_version() {
+        let pre_release = Semantic::parse("v1.0.0-rc.1", "v").unwrap();
+        let release = Semantic::parse("v1.0.0", "v").unwrap();
+        let previous = Semantic::parse("v0.9.9", "v").unwrap();
+
+        assert!(pre_release < release);
+        assert!(previous < pre_release);
+    }
+
+    #[test]
+    fn numeric_identifier_is_lower_than_alphanumeric() {
+        let numeric = Semantic::parse("v1.0.0-1", "v").unwrap();
+        let alphanumeric = Semantic::parse("v1.0.0-a", "v").unwrap();
+
+        assert!(numeric < alphanumeric);
+    }
+
+    #[test]
+    fn sort_multi_part_pre_release_identifiers_independently() {
+        let expected = vec![
+            "v1.0.0-1.2.3",
+            "v1.0.0-1.2.10",
+            "v1.0.0-1.10.0",
+            "v1.0.0-alpha.1.2",
+            "v1.0.0-alpha.1.10",
+            "v1.0.0-alpha.1.10.1",
+            "v1.0.0-alpha.2",
+        ];
+
+        assert_eq!(
+            expected,
+            sorted(&[
+                "v1.0.0-alpha.1.10",
+                "v1.0.0-1.10.0",
+                "v1.0.0-alpha.2",
+                "v1.0.0-1.2.10",
+                "v1.0.0-alpha.1.10.1",
+                "v1.0.0-alpha.1.2",
+                "v1.0.0-1.2.3",
+            ])
+        );
+    }
+
+    #[test]
+    fn increment_pre_release_changes_last_numeric_identifier_only() {
+        let mut version = Semantic::parse("v1.0.0-1.2.3", "v").unwrap();
+        version.increment_pre_release().unwrap();
+
+        assert_eq!("v1.0.0-1.2.4", version.to_string());
+    }
+
+    #[test]
+    fn increment_pre_release_across_digit_boundary() {
+        let mut version = Semantic::parse("v1.0.0-alpha.1.9", "v").unwrap();
+        let before = version.clone();
+        version.increment_pre_release().unwrap();
+
+        assert_eq!("v1.0.0-alpha.1.10", version.to_string());
+        assert!(before < version);
+    }
+
+    #[test]
+    fn increment_pre_release_skips_trailing_alphanumeric() {
+        let mut version = Semantic::parse("v1.0.0-rc.1.hotfix", "v").unwrap();
+        version.increment_pre_release().unwrap();
+
+        assert_eq!("v1.0.0-rc.2.hotfix", version.to_string());
+    }
+
+    #[test]
+    fn increment_pre_release_adds_numeric_identifier() {
+        let mut version = Semantic::parse("v1.0.0-alpha", "v").unwrap();
+        version.increment_pre_release().unwrap();
+
+        assert_eq!("v1.0.0-alpha.1", version.to_string());
+    }
+
+    #[test]
+    fn reset_pre_release_counter_to_one() {
+        let cases = [
+            ("v1.2.0-rc.5", "v1.2.0-rc.1"),
+            ("v1.2.0-rc.5.hotfix", "v1.2.0-rc.1.hotfix"),
+            ("v1.2.0-alpha", "v1.2.0-alpha.1"),
+        ];
+        for (tag, expected) in cases {
+            let mut version = Semantic::parse(tag, "v").unwrap();
+            version.reset_pre_release_counter().unwrap();
+
+            assert_eq!(expected, version.to_string(), "{}", tag);
+        }
+        assert!(matches!(
+            Semantic::parse("v1.2.0", "v")
+                .unwrap()
+                .reset_pre_release_counter(),
+            Err(Error::NotPreRelease(_))
+        ));
+    }
+
+    #[test]
+    fn increment_pre_release_error_for_release_version() {
+        let mut version = Semantic::parse("v1.0.0", "v").unwrap();
+
+        assert_eq!(
+            "Version v1.0.0 is not a pre-release",
+            version.increment_pre_release().unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn latest_pre_release_is_last_after_sort() {
+        let expected = vec!["v0.9.0", "v1.0.0-alpha", "v1.0.0-beta", "v1.0.0-rc.1"];
+
+        assert_eq!(
+            expected,
+            sorted(&["v1.0.0-rc.1", "v0.9.0", "v1.0.0-beta", "v1.0.0-alpha"])
+        );
+    }
+
+    fn version(tag: &str) -> Semantic {
+        let prefix = tag.find(|c: char| c.is_ascii_digit()).unwrap();
+        Semantic::parse(tag, &tag[..prefix]).unwrap()
+    }
+
+    #[test]
+    fn ordering_ignores_the_prefix() {
+        let cases = [
+            ("v1.0.0", "release-1.0.0", Ordering::Equal),
+            ("release-1.0.0", "v1.0.0", Ordering::Equal),
+            ("1.0.0", "v1.0.0", Ordering::Equal),
+            ("v1.0.0-rc.1", "release-1.0.0-rc.1", Ordering::Equal),
+            ("release-1.0.0", "v1.0.1", Ordering::Less),
+            ("release-1.0.0", "v0.9.9", Ordering::Greater),
+            ("v2.0.0", "release-10.0.0", Ordering::Less),
+            ("z1.2.3", "a1.2.4", Ordering::Less),
+            ("release-1.0.0-rc.1", "v1.0.0", Ordering::Less),
+            ("v1.0.0-rc.1", "release-1.0.0-beta.2", Ordering::Greater),
+            ("v1.0.0-alpha.1", "release-1.0.0-alpha", Ordering::Greater),
+        ];
+        for (a, b, expected) in cases {
+            assert_eq!(expected, version(a).cmp(&version(b)), "{} {}", a, b);
+            assert_eq!(
+                expected.reverse(),
+                version(b).cmp(&version(a)),
+                "{} {}",
+                b,
+                a
+            );
+            assert_eq!(
+                expected == Ordering::Equal,
+                version(a) == version(b),
+                "{} {}",
+                a,
+                b
+            );
+        }
+    }
+
+    #[test]
+    fn ordering_ignores_the_pre_release_style() {
+        let a = Semantic::parse_with_style("v1.2.3.rc1", "v", PreReleaseStyle::DotSuffix).unwrap();
+        let b = Semantic::parse("release-1.2.3-rc.1", "release-").unwrap();
+
+        assert_eq!(Ordering::Equal, a.cmp(&b));
+    }
+
+    #[test]
+    fn sort_mixed_prefixes_by_version() {
+        let mut versions: Vec<Semantic> = ["release-1.2.0", "v1.10.0", "v1.3.0", "release-0.9.0"]
+            .iter()
+            .map(|tag| version(tag))
+            .collect();
+
+        Semantic::sort(&mut versions);
+
+        let sorted: Vec<String> = versions.iter().map(|v| v.to_string()).collect();
+        assert_eq!(
+            vec!["release-0.9.0", "release-1.2.0", "v1.3.0", "v1.10.0"],
+            sorted
+        );
     }
-    // #[error("Version must be a number")]
-    // MustBeNumber,
 }
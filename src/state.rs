@@ -0,0 +1,112 @@
+//! Release state kept in a file
+//!
+//! For repositories that do not use version tags the last released
+//! version and the prefix can be kept in a JSON state file
+//! (e.g. `.nextsv-state.json`):
+//!
+//! ```json
+//! {
+//!   "version": "1.2.3",
+//!   "prefix": "v",
+//!   "commit": "2f1e6c0d3c8f4f0fbb2b6b0b5e4f0d2c1a9e8d7c"
+//! }
+//! ```
+//!
+//! The commit is the commit released as the version. Commits after it are
+//! used to calculate the next version. If no commit is recorded all
+//! commits are used.
+//!
+
+use std::{fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, Semantic};
+
+/// The release state read from or written to a state file
+///
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct State {
+    /// the last released version without the prefix e.g. "1.2.3"
+    pub version: String,
+    /// the prefix used for the version
+    #[serde(default)]
+    pub prefix: String,
+    /// the commit released as the version
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+}
+
+impl State {
+    /// Create the state for a released version
+    ///
+    pub fn new(version: &Semantic, commit: Option<git2::Oid>) -> State {
+        let prefix = version.version_prefix().to_string();
+        let version = version.to_string();
+        State {
+            version: version[prefix.len()..].to_string(),
+            prefix,
+            commit: commit.map(|oid| oid.to_string()),
+        }
+    }
+
+    /// Read the state from the file at path
+    ///
+    /// ## Error
+    ///
+    /// Report error if the file cannot be read or is not valid.
+    ///
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<State, Error> {
+        let content = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Write the state to the file at path
+    ///
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let mut content = serde_json::to_string_pretty(self)?;
+        content.push('\n');
+        fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// Report the version in the state
+    ///
+    /// ## Error
+    ///
+    /// Report error if the version is not a semantic version.
+    ///
+    pub fn semantic(&self) -> Result<Semantic, Error> {
+        Semantic::parse(&format!("{}{}", self.prefix, self.version), &self.prefix)
+    }
+
+    /// Report the commit in the state
+    ///
+    /// ## Error
+    ///
+    /// Report error if the commit is not a valid object id.
+    ///
+    pub fn commit(&self) -> Result<Option<git2::Oid>, Error> {
+        match &self.commit {
+            Some(commit) => Ok(Some(git2::Oid::from_str(commit)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn state_round_trips_through_json() {
+        let version = Semantic::parse("v1.2.3", "v").unwrap();
+        let state = State::new(&version, None);
+
+        let json = serde_json::to_string(&state).unwrap();
+
+        assert_eq!(r#"{"version":"1.2.3","prefix":"v"}"#, json);
+        let read: State = serde_json::from_str(&json).unwrap();
+        assert_eq!(version, read.semantic().unwrap());
+    }
+}
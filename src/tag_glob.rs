@@ -0,0 +1,109 @@
+//! Version tags matched by a glob instead of a prefix
+//!
+//! The glob has a single `*` matching the version number. The text before
+//! the `*` is the version prefix and the text after it must end the tag:
+//!
+//! - `release/v*` matches `release/v1.2.3`
+//! - `v*-stable` matches `v1.2.3-stable`
+//!
+
+use std::fmt;
+
+use crate::{Error, Semantic};
+
+/// A glob identifying version tags (e.g. `release/v*`)
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagGlob {
+    prefix: String,
+    suffix: String,
+}
+
+impl TagGlob {
+    /// Create the glob from the pattern
+    ///
+    /// ## Error
+    ///
+    /// Report `Error::InvalidTagGlob` if the pattern does not have a
+    /// single `*`.
+    ///
+    pub fn new(pattern: &str) -> Result<TagGlob, Error> {
+        match pattern.split_once('*') {
+            Some((prefix, suffix)) if !suffix.contains('*') => Ok(TagGlob {
+                prefix: prefix.to_string(),
+                suffix: suffix.to_string(),
+            }),
+            _ => Err(Error::InvalidTagGlob(pattern.to_string())),
+        }
+    }
+
+    /// Report the version prefix (the text before the `*`)
+    ///
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Report the part of the tag name to parse as the version if the
+    /// name matches the glob
+    ///
+    /// The part includes the prefix and leaves out the suffix, e.g.
+    /// `v1.2.3` for `v1.2.3-stable` matched by `v*-stable`.
+    ///
+    pub fn version_part<'a>(&self, name: &'a str) -> Option<&'a str> {
+        if name.len() <= self.prefix.len() + self.suffix.len() {
+            return None;
+        }
+        if name.starts_with(&self.prefix) {
+            name.strip_suffix(&self.suffix)
+        } else {
+            None
+        }
+    }
+
+    /// The name of the tag for the version
+    ///
+    pub fn tag_name(&self, version: &Semantic) -> String {
+        format!("{}{}", version.tag_name(), self.suffix)
+    }
+}
+
+impl fmt::Display for TagGlob {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}*{}", self.prefix, self.suffix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn version_part_strips_the_suffix() {
+        let glob = TagGlob::new("v*-stable").unwrap();
+
+        assert_eq!(Some("v1.2.3"), glob.version_part("v1.2.3-stable"));
+        assert_eq!(None, glob.version_part("v1.2.3"));
+        assert_eq!(None, glob.version_part("v-stable"));
+        assert_eq!("v", glob.prefix());
+    }
+
+    #[test]
+    fn tag_name_adds_the_suffix() {
+        let glob = TagGlob::new("v*-stable").unwrap();
+        let version = Semantic::parse("v1.2.3", "v").unwrap();
+
+        assert_eq!("v1.2.3-stable", glob.tag_name(&version));
+        assert_eq!("v*-stable", glob.to_string());
+    }
+
+    #[test]
+    fn new_requires_a_single_wildcard() {
+        for pattern in ["v1.2.3", "release/*/v*"] {
+            assert!(
+                matches!(TagGlob::new(pattern), Err(Error::InvalidTagGlob(_))),
+                "{}",
+                pattern
+            );
+        }
+    }
+}
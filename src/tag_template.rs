@@ -0,0 +1,184 @@
+//! Names of the tags created from a template
+//!
+//! The template names the tag with placeholders for the version:
+//!
+//! - `{prefix}` - the version prefix (e.g. `v`)
+//! - `{version}` - the version number with any pre-release (e.g. `1.2.3-rc.1`)
+//! - `{core}` - the version number without the pre-release (e.g. `1.2.3`)
+//! - `{major}`, `{minor}` and `{patch}` - the components of the version number
+//!
+//! For example `release/{prefix}{version}` names the tag `release/v1.2.3`.
+//!
+//! The version must be written once as `{version}`, `{core}` or
+//! `{major}.{minor}.{patch}` and the rest of the template may only use
+//! `{prefix}`, so the tags created can be read back with the equivalent
+//! `TagGlob` (e.g. `release/v*`). A template without the pre-release
+//! names release versions only.
+//!
+
+use std::fmt;
+
+use crate::{Error, Semantic, TagGlob};
+
+const VERSION: &str = "{version}";
+const CORE: &str = "{core}";
+const COMPONENTS: &str = "{major}.{minor}.{patch}";
+const PREFIX: &str = "{prefix}";
+
+/// A template for the names of version tags (e.g. `release/{prefix}{version}`)
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagTemplate {
+    template: String,
+    prefix: String,
+    glob: TagGlob,
+    pre_release: bool,
+}
+
+impl TagTemplate {
+    /// Create the template for the version prefix
+    ///
+    /// ## Error
+    ///
+    /// Report `Error::InvalidTagTemplate` if the version is not written
+    /// once or a placeholder is not known.
+    ///
+    pub fn new(template: &str, version_prefix: &str) -> Result<TagTemplate, Error> {
+        let invalid = || Error::InvalidTagTemplate(template.to_string());
+        let normalised = template.replace(COMPONENTS, CORE);
+        let versions = normalised.matches(VERSION).count();
+        let cores = normalised.matches(CORE).count();
+        let (placeholder, pre_release) = match (versions, cores) {
+            (1, 0) => (VERSION, true),
+            (0, 1) => (CORE, false),
+            _ => return Err(invalid()),
+        };
+        let (before, after) = normalised.split_once(placeholder).ok_or_else(invalid)?;
+        let before = before.replace(PREFIX, version_prefix);
+        let after = after.replace(PREFIX, version_prefix);
+        if [&before, &after]
+            .iter()
+            .any(|part| part.contains(['{', '}']))
+        {
+            return Err(invalid());
+        }
+        let glob = TagGlob::new(&format!("{}*{}", before, after)).map_err(|_| invalid())?;
+        Ok(TagTemplate {
+            template: template.to_string(),
+            prefix: version_prefix.to_string(),
+            glob,
+            pre_release,
+        })
+    }
+
+    /// Report the glob matching the tags named by the template
+    ///
+    pub fn glob(&self) -> &TagGlob {
+        &self.glob
+    }
+
+    /// The name of the tag for the version
+    ///
+    /// ## Error
+    ///
+    /// Report `Error::PreReleaseNotInTemplate` if the version is a
+    /// pre-release and the template leaves out the pre-release.
+    ///
+    pub fn tag_name(&self, version: &Semantic) -> Result<String, Error> {
+        if version.is_pre_release() && !self.pre_release {
+            return Err(Error::PreReleaseNotInTemplate(
+                self.template.clone(),
+                version.to_string(),
+            ));
+        }
+        let core = format!(
+            "{}.{}.{}",
+            version.major(),
+            version.minor(),
+            version.patch()
+        );
+        let full = match version.pre_release() {
+            Some(pre_release) => format!("{}-{}", core, pre_release),
+            None => core.clone(),
+        };
+        Ok(self
+            .template
+            .replace(PREFIX, &self.prefix)
+            .replace(VERSION, &full)
+            .replace(CORE, &core)
+            .replace("{major}", &version.major().to_string())
+            .replace("{minor}", &version.minor().to_string())
+            .replace("{patch}", &version.patch().to_string()))
+    }
+}
+
+impl fmt::Display for TagTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.template)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tag_name_fills_in_the_placeholders() {
+        let version = Semantic::parse("v1.2.3-rc.1", "v").unwrap();
+        let cases = [
+            ("{prefix}{version}", "v1.2.3-rc.1", "v*"),
+            (
+                "release/{prefix}{version}",
+                "release/v1.2.3-rc.1",
+                "release/v*",
+            ),
+            (
+                "{prefix}{version}-stable",
+                "v1.2.3-rc.1-stable",
+                "v*-stable",
+            ),
+        ];
+        for (pattern, name, glob) in cases {
+            let template = TagTemplate::new(pattern, "v").unwrap();
+
+            assert_eq!(name, template.tag_name(&version).unwrap(), "{}", pattern);
+            assert_eq!(glob, template.glob().to_string(), "{}", pattern);
+        }
+    }
+
+    #[test]
+    fn core_templates_name_releases_only() {
+        let release = Semantic::parse("v1.2.3", "v").unwrap();
+        let pre_release = Semantic::parse("v1.2.3-rc.1", "v").unwrap();
+
+        for pattern in ["{prefix}{core}", "{prefix}{major}.{minor}.{patch}"] {
+            let template = TagTemplate::new(pattern, "v").unwrap();
+
+            assert_eq!("v1.2.3", template.tag_name(&release).unwrap());
+            assert!(matches!(
+                template.tag_name(&pre_release),
+                Err(Error::PreReleaseNotInTemplate(_, _))
+            ));
+        }
+    }
+
+    #[test]
+    fn new_requires_the_version_once() {
+        for pattern in [
+            "{prefix}",
+            "{version}-{version}",
+            "{major}/{prefix}{version}",
+            "{prefix}{version}-{branch}",
+            "*/{version}",
+        ] {
+            assert!(
+                matches!(
+                    TagTemplate::new(pattern, "v"),
+                    Err(Error::InvalidTagTemplate(_))
+                ),
+                "{}",
+                pattern
+            );
+        }
+    }
+}
@@ -0,0 +1,164 @@
+//! Helpers for testing the version calculation
+//!
+//! Available with the `test-util` feature.
+//!
+//! ## Example
+//!
+//! ```rust
+//! # fn main() -> Result<(), nextsv::Error> {
+//! use nextsv::{test_util::TempRepo, Level};
+//!
+//! let repo = TempRepo::new()?;
+//! repo.commit("chore: initial commit", &["README.md"])?;
+//! repo.tag("v1.0.0")?;
+//! repo.commit("feat: add a feature", &["README.md"])?;
+//!
+//! let answer = repo.calculator("v")?.walk_commits()?.compute();
+//!
+//! assert_eq!(Level::Minor, answer.bump_level);
+//! assert_eq!("v1.1.0", answer.version_number.to_string());
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{fs, path::Path};
+
+use git2::{Oid, Repository, Signature};
+use tempfile::TempDir;
+
+use crate::{Error, VersionCalculator};
+
+/// A git repository in a temporary directory
+///
+/// The directory is removed when the TempRepo is dropped.
+///
+pub struct TempRepo {
+    dir: TempDir,
+    repo: Repository,
+}
+
+impl TempRepo {
+    /// Create an empty repository in a new temporary directory
+    ///
+    pub fn new() -> Result<TempRepo, Error> {
+        let dir = TempDir::new()?;
+        let repo = Repository::init(dir.path())?;
+        Ok(TempRepo { dir, repo })
+    }
+
+    /// Report the path to the repository
+    ///
+    pub fn path(&self) -> &Path {
+        self.dir.path()
+    }
+
+    /// Report the repository
+    ///
+    pub fn repo(&self) -> &Repository {
+        &self.repo
+    }
+
+    /// Commit changes to the files on HEAD with the message
+    ///
+    /// The message is appended to each of the files so that every
+    /// file listed is changed by the commit.
+    ///
+    pub fn commit(&self, message: &str, files: &[&str]) -> Result<Oid, Error> {
+        self.commit_with(&signature()?, message, files)
+    }
+
+    /// Commit changes to the files on HEAD as the author
+    ///
+    pub fn commit_as(
+        &self,
+        name: &str,
+        email: &str,
+        message: &str,
+        files: &[&str],
+    ) -> Result<Oid, Error> {
+        self.commit_with(&Signature::now(name, email)?, message, files)
+    }
+
+    fn commit_with(
+        &self,
+        signature: &Signature<'_>,
+        message: &str,
+        files: &[&str],
+    ) -> Result<Oid, Error> {
+        let mut index = self.repo.index()?;
+        for file in files {
+            let path = self.path().join(file);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            let content = match fs::read_to_string(&path) {
+                Ok(content) => format!("{}{}\n", content, message),
+                Err(_) => format!("{}\n", message),
+            };
+            fs::write(&path, content)?;
+            index.add_path(Path::new(file))?;
+        }
+        index.write()?;
+        let tree = self.repo.find_tree(index.write_tree()?)?;
+        let parent = match self.repo.head() {
+            Ok(head) => Some(head.peel_to_commit()?),
+            Err(_) => None,
+        };
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        Ok(self
+            .repo
+            .commit(Some("HEAD"), signature, signature, message, &tree, &parents)?)
+    }
+
+    /// Create a lightweight tag on HEAD
+    ///
+    pub fn tag(&self, name: &str) -> Result<(), Error> {
+        let head = self.repo.head()?.peel_to_commit()?;
+        self.repo.tag_lightweight(name, head.as_object(), false)?;
+        Ok(())
+    }
+
+    /// Create an annotated tag on HEAD
+    ///
+    pub fn annotated_tag(&self, name: &str, message: &str) -> Result<(), Error> {
+        let head = self.repo.head()?.peel_to_commit()?;
+        self.repo
+            .tag(name, head.as_object(), &signature()?, message, false)?;
+        Ok(())
+    }
+
+    /// Create a branch on HEAD
+    ///
+    pub fn branch(&self, name: &str) -> Result<(), Error> {
+        let head = self.repo.head()?.peel_to_commit()?;
+        self.repo.branch(name, &head, false)?;
+        Ok(())
+    }
+
+    /// Detach HEAD at the commit currently checked out
+    ///
+    pub fn detach(&self) -> Result<(), Error> {
+        let head = self.repo.head()?.peel_to_commit()?;
+        self.repo.set_head_detached(head.id())?;
+        Ok(())
+    }
+
+    /// Add a note to the commit in the notes ref
+    ///
+    pub fn note(&self, notes_ref: &str, id: Oid, note: &str) -> Result<(), Error> {
+        let signature = signature()?;
+        self.repo
+            .note(&signature, &signature, Some(notes_ref), id, note, false)?;
+        Ok(())
+    }
+
+    /// Create a VersionCalculator for the repository
+    ///
+    pub fn calculator(&self, version_prefix: &str) -> Result<VersionCalculator, Error> {
+        VersionCalculator::new_in(self.path(), version_prefix)
+    }
+}
+
+fn signature() -> Result<Signature<'static>, Error> {
+    Ok(Signature::now("nextsv", "nextsv@example.com")?)
+}
@@ -0,0 +1,1403 @@
+use std::{collections::HashMap, ffi::OsString, time::Duration};
+
+use nextsv::{
+    test_util::TempRepo, Error, ForceLevel, Level, ParsedCommit, PlanOptions, PreReleaseStyle,
+    ReleaseTags, RevertBump, Semantic, State, TagGlob, TagIssue, TagTemplate, TypeHierarchy,
+    UnknownType, VersionCalculator,
+};
+
+#[test]
+fn feature_commit_bumps_minor() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.tag("v1.2.3")?;
+    repo.commit("fix: correct a bug", &["src/lib.rs"])?;
+    repo.commit("feat: add a feature", &["src/lib.rs"])?;
+
+    let answer = repo.calculator("v")?.walk_commits()?.compute();
+
+    assert_eq!(Level::Minor, answer.bump_level);
+    assert_eq!("v1.3.0", answer.version_number.to_string());
+    Ok(())
+}
+
+#[test]
+fn answer_reports_the_head_oid() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.tag("v1.2.3")?;
+    let head = repo.commit("feat: add a feature", &["src/lib.rs"])?;
+
+    let calculator = repo.calculator("v")?;
+    assert_eq!(None, calculator.head_oid());
+    let calculator = calculator.walk_commits()?;
+
+    assert_eq!(Some(head), calculator.head_oid());
+    assert_eq!(head.to_string(), calculator.compute().head_oid);
+    Ok(())
+}
+
+#[test]
+fn commits_before_the_tag_are_not_counted() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("feat!: initial interface", &["README.md"])?;
+    repo.tag("v1.0.0")?;
+    repo.commit("docs: update the readme", &["README.md"])?;
+
+    let calculator = repo.calculator("v")?.walk_commits()?;
+
+    assert!(!calculator.breaking());
+    assert_eq!(1, calculator.count_commits_by_type("docs"));
+    assert_eq!(Level::Patch, calculator.compute().bump_level);
+    Ok(())
+}
+
+#[test]
+fn required_files_found_in_subdirectory_commits() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md", "CHANGES.md"])?;
+    repo.tag("v1.0.0")?;
+    repo.commit("feat: add a feature", &["src/lib.rs"])?;
+    repo.commit("docs: update the changes", &["CHANGES.md"])?;
+
+    let calculator = repo.calculator("v")?.walk_commits()?;
+
+    assert!(calculator
+        .has_required(vec![OsString::from("CHANGES.md")], TypeHierarchy::Feature)
+        .is_ok());
+    assert!(calculator
+        .has_required(vec![OsString::from("README.md")], TypeHierarchy::Feature)
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn no_version_tag_is_an_error() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("feat: add a feature", &["README.md"])?;
+
+    assert!(matches!(repo.calculator("v"), Err(Error::NoVersionTag)));
+    Ok(())
+}
+
+#[test]
+fn configured_prefix_not_set() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+
+    assert_eq!(None, VersionCalculator::configured_prefix(repo.path())?);
+    Ok(())
+}
+
+#[test]
+fn breaking_change_out_of_scope_is_not_breaking() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.tag("v1.2.3")?;
+    repo.commit("feat(other-pkg)!: replace the interface", &["other/lib.rs"])?;
+    repo.commit("fix(pkg): correct a bug", &["pkg/lib.rs"])?;
+
+    let calculator = repo
+        .calculator("v")?
+        .breaking_requires_scope("pkg")
+        .walk_commits()?;
+    let answer = calculator.compute();
+
+    assert!(!calculator.breaking());
+    assert_eq!(Level::Minor, answer.bump_level);
+    assert_eq!("v1.3.0", answer.version_number.to_string());
+    Ok(())
+}
+
+#[test]
+fn breaking_change_in_scope_is_breaking() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.tag("v1.2.3")?;
+    repo.commit("feat(pkg)!: replace the interface", &["pkg/lib.rs"])?;
+
+    let answer = repo
+        .calculator("v")?
+        .breaking_requires_scope("pkg")
+        .walk_commits()?
+        .compute();
+
+    assert_eq!(Level::Major, answer.bump_level);
+    Ok(())
+}
+
+#[test]
+fn breaking_change_without_scope_is_breaking() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.tag("v1.2.3")?;
+    repo.commit("fix!: change the default", &["README.md"])?;
+
+    let answer = repo
+        .calculator("v")?
+        .breaking_requires_scope("pkg")
+        .walk_commits()?
+        .compute();
+
+    assert_eq!(Level::Major, answer.bump_level);
+    Ok(())
+}
+
+#[test]
+fn detached_head_computes_the_bump() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.tag("v1.2.3")?;
+    repo.commit("fix: correct a bug", &["README.md"])?;
+    repo.commit("feat: add a feature", &["README.md"])?;
+    let branch = repo.calculator("v")?.head_branch()?;
+    repo.detach()?;
+
+    let calculator = repo.calculator("v")?.walk_commits()?;
+
+    assert!(repo.repo().head_detached()?);
+    assert_eq!("v1.3.0", calculator.compute().version_number.to_string());
+    assert_eq!(2, calculator.distance());
+    assert_ne!(branch, calculator.head_branch()?);
+    assert_eq!(calculator.head_short_id(None)?, calculator.head_branch()?);
+    Ok(())
+}
+
+#[test]
+fn linked_worktree_and_subdirectory_are_discovered() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.tag("v1.2.3")?;
+    repo.commit("feat: add a feature", &["src/lib.rs"])?;
+    let linked = tempfile::TempDir::new()?;
+    let worktree_path = linked.path().join("linked");
+    repo.repo().worktree("linked", &worktree_path, None)?;
+    assert!(worktree_path.join(".git").is_file());
+
+    for path in [worktree_path.clone(), repo.path().join("src")] {
+        let answer = VersionCalculator::new_in(&path, "v")?
+            .walk_commits()?
+            .compute();
+
+        assert_eq!("v1.3.0", answer.version_number.to_string(), "{:?}", path);
+    }
+    Ok(())
+}
+
+#[test]
+fn base_ref_counts_only_the_branch_commits() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.tag("v1.2.3")?;
+    repo.commit("feat: add a feature on main", &["src/lib.rs"])?;
+    repo.branch("main")?;
+    repo.commit("fix: correct a bug on the branch", &["src/lib.rs"])?;
+
+    let calculator = repo.calculator("v")?.base_ref("main").walk_commits()?;
+
+    assert_eq!(0, calculator.count_commits_by_type("feat"));
+    assert_eq!(1, calculator.count_commits_by_type("fix"));
+    let answer = calculator.compute();
+    assert_eq!(Level::Patch, answer.bump_level);
+    assert_eq!("v1.2.4", answer.version_number.to_string());
+    Ok(())
+}
+
+#[test]
+fn branch_tags_only_ignores_tags_on_a_sibling_branch() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.tag("v1.2.3")?;
+    let main = repo.calculator("v")?.head_branch()?;
+    repo.branch("release-2")?;
+    repo.repo().set_head("refs/heads/release-2")?;
+    repo.commit("feat!: change the api", &["src/lib.rs"])?;
+    repo.tag("v2.0.0")?;
+    repo.repo().set_head(&format!("refs/heads/{}", main))?;
+    repo.commit("fix: correct a bug", &["src/main.rs"])?;
+
+    assert_eq!("v2.0.0", repo.calculator("v")?.name().to_string());
+    let calculator = repo.calculator("v")?.branch_tags_only()?;
+    assert_eq!("v1.2.3", calculator.name().to_string());
+
+    let calculator = calculator.walk_commits()?;
+    let answer = calculator.compute();
+    assert_eq!("v1.2.4", answer.version_number.to_string());
+    assert!(calculator.check_regression(&answer).is_ok());
+    Ok(())
+}
+
+#[test]
+fn release_tags_selects_the_kind_of_baseline_tag() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.annotated_tag("v1.2.3", "Release v1.2.3")?;
+    repo.commit("fix: correct a bug", &["src/lib.rs"])?;
+    repo.tag("v1.2.4")?;
+    repo.commit("feat: add a feature", &["src/lib.rs"])?;
+
+    let cases = [
+        (ReleaseTags::Any, "v1.2.4", "v1.3.0", 1),
+        (ReleaseTags::Annotated, "v1.2.3", "v1.3.0", 2),
+        (ReleaseTags::Lightweight, "v1.2.4", "v1.3.0", 1),
+    ];
+    for (release_tags, baseline, next, distance) in cases {
+        let calculator = repo
+            .calculator("v")?
+            .release_tags(release_tags)?
+            .walk_commits()?;
+
+        assert_eq!(baseline, calculator.baseline_tag(), "{:?}", release_tags);
+        assert_eq!(distance, calculator.distance(), "{:?}", release_tags);
+        let answer = calculator.compute();
+        assert_eq!(
+            next,
+            answer.version_number.to_string(),
+            "{:?}",
+            release_tags
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn merge_base_counts_only_the_tip_commits() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.tag("v1.2.3")?;
+    repo.commit("feat: add a feature on both", &["src/lib.rs"])?;
+    let main = repo.calculator("v")?.head_branch()?;
+    repo.branch("train")?;
+    repo.repo().set_head("refs/heads/train")?;
+    let train_fix = repo.commit("fix: correct a bug on the train", &["src/lib.rs"])?;
+    let train_perf = repo.commit("perf: speed up on the train", &["src/lib.rs"])?;
+    repo.repo().set_head(&format!("refs/heads/{}", main))?;
+    repo.commit("feat!: change the api on main", &["src/main.rs"])?;
+
+    let calculator = repo
+        .calculator("v")?
+        .merge_base(&main, "train")
+        .walk_commits()?;
+
+    let mut walked: Vec<_> = calculator.commits().iter().map(|c| c.oid).collect();
+    walked.sort();
+    let mut expected = vec![Some(train_fix), Some(train_perf)];
+    expected.sort();
+    assert_eq!(expected, walked);
+    assert_eq!(Some(train_perf), calculator.head_oid());
+    assert!(!calculator.breaking());
+    assert_eq!("v1.2.4", calculator.compute().version_number.to_string());
+    Ok(())
+}
+
+#[test]
+fn malformed_version_tag_is_reported_as_skipped() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.tag("v1.2.3")?;
+    repo.tag("v1.2.x")?;
+    repo.tag("release-1")?;
+
+    let skipped = repo.calculator("v")?.skipped_tags()?;
+
+    assert_eq!(1, skipped.len());
+    assert_eq!("v1.2.x", skipped[0].0);
+    assert!(matches!(&skipped[0].1, Error::MustBeNumber(item) if item == "x"));
+    Ok(())
+}
+
+#[test]
+fn words_starting_with_the_prefix_are_not_version_tags() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.tag("v1.2.3")?;
+    repo.commit("feat: add a feature", &["src/lib.rs"])?;
+    repo.tag("vnotes")?;
+    repo.tag("vlatest")?;
+    repo.tag("version-note")?;
+
+    let calculator = repo.calculator("v")?;
+
+    assert_eq!("v1.2.3", calculator.baseline_tag());
+    assert!(calculator.skipped_tags()?.is_empty());
+    assert_eq!(
+        vec!["v1.2.3".to_string()],
+        calculator
+            .all_versions()?
+            .iter()
+            .map(|version| version.to_string())
+            .collect::<Vec<_>>()
+    );
+    Ok(())
+}
+
+#[test]
+fn version_tagged_on_different_commits_is_ambiguous() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    let first = repo.commit("chore: initial commit", &["README.md"])?;
+    repo.tag("v1.2.3")?;
+    let second = repo.commit("fix: correct a bug", &["README.md"])?;
+    repo.tag("V1.2.3")?;
+
+    let calculator = VersionCalculator::new_ignore_case_in(repo.path(), "v")?;
+
+    match calculator.check_tags() {
+        Err(Error::AmbiguousTag(version, a, b)) => {
+            assert!(version.eq_ignore_ascii_case("v1.2.3"), "{}", version);
+            let mut found = vec![a, b];
+            found.sort();
+            let mut expected = vec![first, second];
+            expected.sort();
+            assert_eq!(expected, found);
+        }
+        other => panic!("expected an ambiguous tag error, got {:?}", other),
+    }
+    Ok(())
+}
+
+#[test]
+fn versions_on_one_commit_are_not_ambiguous() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.tag("v1.2.3")?;
+    repo.tag("V1.2.3")?;
+
+    let calculator = VersionCalculator::new_ignore_case_in(repo.path(), "v")?;
+    assert!(calculator.check_tags().is_ok());
+    Ok(())
+}
+
+#[test]
+fn tags_with_leading_zeros_are_not_version_tags() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.tag("v1.2.3")?;
+    repo.commit("fix: correct a bug", &["README.md"])?;
+    repo.tag("v01.2.4")?;
+    repo.tag("v1.02.4")?;
+
+    let calculator = repo.calculator("v")?;
+
+    assert_eq!("v1.2.3", calculator.name().to_string());
+    let versions: Vec<String> = calculator
+        .all_versions()?
+        .iter()
+        .map(|version| version.to_string())
+        .collect();
+    assert_eq!(vec!["v1.2.3"], versions);
+    let mut skipped = calculator.skipped_tags()?;
+    skipped.sort_by(|(a, _), (b, _)| a.cmp(b));
+    assert_eq!(2, skipped.len());
+    assert!(matches!(&skipped[0], (name, Error::LeadingZero(n)) if name == "v01.2.4" && n == "01"));
+    assert!(matches!(&skipped[1], (name, Error::LeadingZero(n)) if name == "v1.02.4" && n == "02"));
+    Ok(())
+}
+
+#[test]
+fn range_summary_counts_the_commits_by_type() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.tag("v1.2.3")?;
+    repo.commit("feat: add a feature", &["src/lib.rs"])?;
+    repo.commit("feat!: replace the interface", &["src/lib.rs"])?;
+    repo.commit("fix: correct a bug", &["src/lib.rs"])?;
+    repo.commit("docs: document the feature", &["README.md"])?;
+    repo.commit("update the readme", &["README.md"])?;
+
+    let calculator = repo.calculator("v")?.walk_commits()?;
+
+    assert_eq!(
+        "5 commits since v1.2.3 (2 feat, 1 fix, 2 other; 1 breaking)",
+        calculator.range_summary()
+    );
+    Ok(())
+}
+
+#[test]
+fn state_file_provides_the_current_version() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    let released = repo.commit("feat: initial release", &["README.md"])?;
+    repo.commit("fix: correct a bug", &["src/lib.rs"])?;
+    let path = repo.path().join(".nextsv-state.json");
+    std::fs::write(
+        &path,
+        format!(
+            r#"{{"version": "1.2.3", "prefix": "v", "commit": "{}"}}"#,
+            released
+        ),
+    )?;
+
+    let state = State::read(&path)?;
+    let calculator = VersionCalculator::from_state(repo.path(), &state)?.walk_commits()?;
+
+    assert_eq!(0, calculator.count_commits_by_type("feat"));
+    let answer = calculator.compute();
+    assert_eq!(Level::Patch, answer.bump_level);
+    assert_eq!("v1.2.4", answer.version_number.to_string());
+    Ok(())
+}
+
+#[test]
+fn release_state_records_the_next_version_at_head() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    let head = repo.commit("feat: add a feature", &["src/lib.rs"])?;
+    let state = State {
+        version: "1.2.3".to_string(),
+        prefix: "v".to_string(),
+        commit: None,
+    };
+    let path = repo.path().join(".nextsv-state.json");
+
+    let calculator = VersionCalculator::from_state(repo.path(), &state)?.walk_commits()?;
+    let answer = calculator.compute();
+    calculator.release_state(&answer)?.write(&path)?;
+
+    let updated = State::read(&path)?;
+    assert_eq!("1.3.0", updated.version);
+    assert_eq!("v", updated.prefix);
+    assert_eq!(Some(head.to_string()), updated.commit);
+    Ok(())
+}
+
+#[test]
+fn breaking_footer_in_non_conventional_commit_is_breaking() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.tag("v1.2.3")?;
+    repo.commit(
+        "Replace the interface\n\nBREAKING CHANGE: the old interface is removed",
+        &["src/lib.rs"],
+    )?;
+
+    let calculator = repo.calculator("v")?.walk_commits()?;
+    assert_eq!(Level::None, calculator.compute().bump_level);
+
+    let calculator = repo
+        .calculator("v")?
+        .breaking_footer_always()
+        .walk_commits()?;
+    let answer = calculator.compute();
+
+    assert!(calculator.breaking());
+    assert_eq!(Level::Major, answer.bump_level);
+    assert_eq!("v2.0.0", answer.version_number.to_string());
+    Ok(())
+}
+
+#[test]
+fn commits_are_reported_as_parsed_entries() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.tag("v1.2.3")?;
+    let feat = repo.commit("feat(cli)!: replace the options", &["src/main.rs"])?;
+    let other = repo.commit("update the readme", &["README.md"])?;
+
+    let calculator = repo.calculator("v")?.walk_commits()?;
+    let mut commits = calculator.commits().to_vec();
+    commits.sort_by_key(|commit| commit.oid != Some(feat));
+    let time = |oid| -> Result<i64, Error> { Ok(repo.repo().find_commit(oid)?.time().seconds()) };
+
+    assert_eq!(
+        vec![
+            ParsedCommit {
+                oid: Some(feat),
+                type_: Some("feat".to_string()),
+                scope: Some("cli".to_string()),
+                description: "replace the options".to_string(),
+                breaking: true,
+                references: vec![],
+                time: time(feat)?,
+            },
+            ParsedCommit {
+                oid: Some(other),
+                type_: None,
+                scope: None,
+                description: "update the readme".to_string(),
+                breaking: false,
+                references: vec![],
+                time: time(other)?,
+            },
+        ],
+        commits
+    );
+    assert_eq!(1, calculator.count_commits_by_type("feat"));
+    assert!(calculator.breaking());
+    Ok(())
+}
+
+#[test]
+fn walk_times_out_when_budget_exceeded() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.tag("v1.2.3")?;
+    repo.commit("feat: add a feature", &["src/lib.rs"])?;
+    repo.commit("fix: correct a bug", &["src/lib.rs"])?;
+
+    let result = repo.calculator("v")?.timeout(Duration::ZERO).walk_commits();
+
+    assert!(matches!(result, Err(Error::WalkTimedOut(_))));
+
+    let calculator = repo
+        .calculator("v")?
+        .timeout(Duration::from_secs(60))
+        .walk_commits()?;
+    assert_eq!(Level::Minor, calculator.compute().bump_level);
+    Ok(())
+}
+
+#[test]
+fn walk_is_refused_during_a_rebase_or_bisect() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.tag("v1.2.3")?;
+    repo.commit("fix: correct a bug", &["src/lib.rs"])?;
+    let git_dir = repo.repo().path().to_path_buf();
+
+    std::fs::create_dir(git_dir.join("rebase-merge"))?;
+    let result = repo.calculator("v")?.walk_commits();
+    assert!(
+        matches!(&result, Err(Error::RepositoryBusy(op)) if op == "rebase"),
+        "{:?}",
+        result
+    );
+    std::fs::remove_dir(git_dir.join("rebase-merge"))?;
+
+    std::fs::write(git_dir.join("BISECT_LOG"), "")?;
+    let result = repo.calculator("v")?.walk_commits();
+    assert!(
+        matches!(&result, Err(Error::RepositoryBusy(op)) if op == "bisect"),
+        "{:?}",
+        result
+    );
+    std::fs::remove_file(git_dir.join("BISECT_LOG"))?;
+
+    let answer = repo.calculator("v")?.walk_commits()?.compute();
+    assert_eq!("v1.2.4", answer.version_number.to_string());
+    Ok(())
+}
+
+#[test]
+fn supplied_current_version_without_tag_walks_all_commits() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.commit("feat: add a feature", &["src/lib.rs"])?;
+
+    let current = Semantic::parse("v1.2.3", "v")?;
+    let calculator = VersionCalculator::with_current_version_in(repo.path(), current)?;
+    let answer = calculator.walk_commits()?.compute();
+
+    assert_eq!(Level::Minor, answer.bump_level);
+    assert_eq!("v1.3.0", answer.version_number.to_string());
+    Ok(())
+}
+
+#[test]
+fn new_or_default_starts_from_the_default_without_tags() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.commit("fix: correct a bug", &["src/lib.rs"])?;
+
+    let default = Semantic::parse("v0.1.0", "v")?;
+    let calculator = VersionCalculator::new_or_default_in(repo.path(), "v", default)?;
+
+    assert_eq!("v0.1.0", calculator.name().to_string());
+    let answer = calculator.walk_commits()?.compute();
+    assert_eq!("v0.1.1", answer.version_number.to_string());
+
+    repo.tag("v0.1.1")?;
+    let default = Semantic::parse("v0.1.0", "v")?;
+    let calculator = VersionCalculator::new_or_default_in(repo.path(), "v", default)?;
+    assert_eq!("v0.1.1", calculator.name().to_string());
+    Ok(())
+}
+
+#[test]
+fn compare_url_needs_a_tagged_baseline() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.commit("feat: add a feature", &["src/lib.rs"])?;
+    let base = "https://github.com/owner/repo";
+
+    let current = Semantic::parse("v0.1.0", "v")?;
+    let calculator = VersionCalculator::with_current_version_in(repo.path(), current)?;
+    let next = calculator.walk_commits()?.compute().version_number;
+    let calculator = VersionCalculator::with_current_version_in(repo.path(), next.clone())?;
+    assert_eq!(None, calculator.compare_url(base, &next)?);
+
+    repo.tag("v0.1.1")?;
+    repo.commit("fix: correct a bug", &["src/lib.rs"])?;
+    let calculator = repo.calculator("v")?.walk_commits()?;
+    let next = calculator.compute().version_number;
+    assert_eq!(
+        Some("https://github.com/owner/repo/compare/v0.1.1...v0.1.2".to_string()),
+        calculator.compare_url(base, &next)?
+    );
+    Ok(())
+}
+
+#[test]
+fn supplied_current_version_stops_at_its_tag() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("feat: initial commit", &["README.md"])?;
+    repo.tag("v1.2.3")?;
+    repo.commit("fix: correct a bug", &["src/lib.rs"])?;
+    repo.tag("v2.0.0")?;
+
+    let current = Semantic::parse("v1.2.3", "v")?;
+    let calculator = VersionCalculator::with_current_version_in(repo.path(), current)?;
+    let answer = calculator.walk_commits()?.compute();
+
+    assert_eq!(Level::Patch, answer.bump_level);
+    assert_eq!("v1.2.4", answer.version_number.to_string());
+    Ok(())
+}
+
+#[test]
+fn head_short_id_length() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.tag("v1.2.3")?;
+    let calculator = repo.calculator("v")?;
+
+    assert_eq!(8, calculator.head_short_id(Some(8))?.len());
+    assert!(calculator.head_short_id(None)?.len() >= 7);
+    assert!(matches!(
+        calculator.head_short_id(Some(41)),
+        Err(Error::InvalidAbbrev(41))
+    ));
+    Ok(())
+}
+
+#[test]
+fn pre_release_starts_series_for_each_core_bump() -> Result<(), Error> {
+    let cases = [
+        ("fix: correct a bug", Level::Patch, "v1.2.1-rc.1"),
+        ("feat: add a feature", Level::Minor, "v1.3.0-rc.1"),
+        ("feat!: replace the interface", Level::Major, "v2.0.0-rc.1"),
+    ];
+    for (message, level, expected) in cases {
+        let repo = TempRepo::new()?;
+        repo.commit("chore: initial commit", &["README.md"])?;
+        repo.tag("v1.2.0")?;
+        repo.commit(message, &["src/lib.rs"])?;
+
+        let answer = repo
+            .calculator("v")?
+            .walk_commits()?
+            .next_pre_release("rc")?;
+
+        assert_eq!(level, answer.bump_level);
+        assert_eq!(expected, answer.version_number.to_string());
+    }
+    Ok(())
+}
+
+#[test]
+fn pre_release_continues_the_series_for_the_stage() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.tag("v1.1.0")?;
+    repo.commit("feat: add a feature", &["src/lib.rs"])?;
+    repo.tag("v1.2.0-alpha.3")?;
+    repo.commit("fix: correct a bug", &["src/lib.rs"])?;
+    repo.tag("v1.2.0-beta.1")?;
+    repo.commit("fix: correct another bug", &["src/lib.rs"])?;
+
+    let mut calculator = repo.calculator("v")?.walk_commits()?;
+
+    assert_eq!(
+        "v1.2.0-beta.2",
+        calculator
+            .next_pre_release("beta")?
+            .version_number
+            .to_string()
+    );
+    assert_eq!(
+        "v1.2.0-rc.1",
+        calculator
+            .next_pre_release("rc")?
+            .version_number
+            .to_string()
+    );
+    assert!(matches!(
+        calculator.next_pre_release("alpha"),
+        Err(Error::VersionNotGreater(_, _))
+    ));
+    Ok(())
+}
+
+#[test]
+fn plan_reports_the_full_picture() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md", "CHANGES.md"])?;
+    repo.tag("v1.2.3")?;
+    repo.commit("feat(cli): add a flag", &["src/main.rs", "CHANGES.md"])?;
+    repo.commit("fix: correct a bug", &["src/lib.rs"])?;
+    repo.commit("docs: update the readme", &["README.md"])?;
+
+    let options = PlanOptions {
+        required: vec![OsString::from("CHANGES.md")],
+        ..PlanOptions::default()
+    };
+    let plan = repo.calculator("v")?.plan(options)?;
+
+    assert_eq!("v1.2.3", plan.previous.to_string());
+    assert_eq!("v1.3.0", plan.next.to_string());
+    assert_eq!(Level::Minor, plan.level);
+    assert_eq!("v1.3.0", plan.tag_name);
+    assert!(plan.required_ok);
+    assert_eq!(3, plan.metrics.commits);
+    assert_eq!(0, plan.metrics.breaking);
+    assert_eq!(Some(&1), plan.metrics.types.get("feat"));
+    assert_eq!(Some(&1), plan.metrics.types.get("docs"));
+    let expected = "\
+## [v1.3.0]
+
+### Features
+
+- **cli:** add a flag
+
+### Bug Fixes
+
+- correct a bug
+";
+    assert_eq!(expected, plan.changelog.to_string());
+
+    let options = PlanOptions {
+        required: vec![OsString::from("Cargo.toml")],
+        ..PlanOptions::default()
+    };
+    let plan = repo.calculator("v")?.plan(options)?;
+    assert!(!plan.required_ok);
+    Ok(())
+}
+
+#[test]
+fn author_stats_count_the_commits_of_each_author() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit_as(
+        "Old Hand",
+        "old@example.com",
+        "chore: initial commit",
+        &["README.md"],
+    )?;
+    repo.tag("v1.2.3")?;
+    repo.commit_as(
+        "Ada",
+        "ada@example.com",
+        "feat: add a feature",
+        &["src/lib.rs"],
+    )?;
+    repo.commit_as(
+        "Grace",
+        "grace@example.com",
+        "fix: correct a bug",
+        &["src/lib.rs"],
+    )?;
+    repo.commit_as(
+        "Ada",
+        "ada@example.com",
+        "docs: explain the feature",
+        &["README.md"],
+    )?;
+
+    let plan = repo.calculator("v")?.plan(PlanOptions::default())?;
+    assert!(plan.metrics.authors.is_empty());
+
+    let plan = repo
+        .calculator("v")?
+        .author_stats()
+        .plan(PlanOptions::default())?;
+    assert_eq!(2, plan.metrics.authors.len());
+    assert_eq!(Some(&2), plan.metrics.authors.get("Ada"));
+    assert_eq!(Some(&1), plan.metrics.authors.get("Grace"));
+    Ok(())
+}
+
+#[test]
+fn squash_body_bullets_are_counted() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.tag("v1.2.3")?;
+    repo.commit(
+        "Add the export feature (#42)\n\n- feat: add csv export\n- feat(cli): add --export\n* fix: close the file\n- tidy up\n",
+        &["src/lib.rs"],
+    )?;
+
+    let answer = repo.calculator("v")?.walk_commits()?.compute();
+    assert_eq!(Level::None, answer.bump_level);
+
+    let calculator = repo.calculator("v")?.expand_squash_body().walk_commits()?;
+    assert_eq!(2, calculator.count_commits_by_type("feat"));
+    assert_eq!(1, calculator.count_commits_by_type("fix"));
+    assert_eq!(4, calculator.commits().len());
+    let answer = calculator.compute();
+    assert_eq!(Level::Minor, answer.bump_level);
+    assert_eq!("v1.3.0", answer.version_number.to_string());
+    Ok(())
+}
+
+#[test]
+fn release_after_a_pre_release_releases_its_core() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.tag("v1.2.0")?;
+    repo.commit("feat: add a feature", &["src/lib.rs"])?;
+    repo.tag("v1.3.0-rc.1")?;
+    repo.commit("fix: correct a bug", &["src/lib.rs"])?;
+
+    let answer = repo.calculator("v")?.walk_commits()?.compute();
+    assert_eq!(Level::Patch, answer.bump_level);
+    assert_eq!("v1.3.0", answer.version_number.to_string());
+
+    repo.commit("feat: add another feature", &["src/lib.rs"])?;
+    let answer = repo.calculator("v")?.walk_commits()?.compute();
+    assert_eq!("v1.3.0", answer.version_number.to_string());
+    let answer = repo
+        .calculator("v")?
+        .walk_commits()?
+        .next_pre_release("rc")?;
+    assert_eq!("v1.3.0-rc.2", answer.version_number.to_string());
+
+    repo.commit("feat!: replace the interface", &["src/lib.rs"])?;
+    let answer = repo.calculator("v")?.walk_commits()?.compute();
+    assert_eq!("v2.0.0", answer.version_number.to_string());
+    let answer = repo
+        .calculator("v")?
+        .walk_commits()?
+        .next_pre_release("rc")?;
+    assert_eq!("v2.0.0-rc.1", answer.version_number.to_string());
+    Ok(())
+}
+
+#[test]
+fn next_version_is_never_lower_than_the_current_version() -> Result<(), Error> {
+    let baselines = [
+        (None, "v0.7.9"),
+        (None, "v1.2.3"),
+        (None, "v1.0.0-rc.1"),
+        (Some("v0.9.0"), "v1.0.0-rc.1"),
+        (None, "v0.1.0-alpha.2"),
+        (Some("v0.0.3"), "v0.1.0-alpha.2"),
+    ];
+    let changes = [
+        None,
+        Some("fix: correct a bug"),
+        Some("feat: add a feature"),
+        Some("feat!: replace the interface"),
+    ];
+    for (release, baseline) in baselines {
+        for change in changes {
+            let repo = TempRepo::new()?;
+            repo.commit("chore: initial commit", &["README.md"])?;
+            if let Some(release) = release {
+                repo.tag(release)?;
+            }
+            repo.commit("feat: add a feature", &["src/lib.rs"])?;
+            repo.tag(baseline)?;
+            if let Some(change) = change {
+                repo.commit(change, &["src/lib.rs"])?;
+            }
+            let current = Semantic::parse(baseline, "v")?;
+            let case = format!("{:?} {} {:?}", release, baseline, change);
+
+            // no change or release
+            let answer = repo.calculator("v")?.walk_commits()?.compute();
+            match change {
+                None => {
+                    assert_eq!(Level::None, answer.bump_level, "{}", case);
+                    assert_eq!(current, answer.version_number, "{}", case);
+                }
+                Some(_) => assert!(answer.version_number > current, "{}", case),
+            }
+
+            // pre-release, continuing the series if the baseline is one
+            let answer = repo
+                .calculator("v")?
+                .walk_commits()?
+                .next_pre_release("rc")?;
+            match change {
+                None => assert_eq!(current, answer.version_number, "{}", case),
+                Some(_) => assert!(answer.version_number > current, "{}", case),
+            }
+
+            // forced changes
+            for level in [ForceLevel::Major, ForceLevel::Minor, ForceLevel::Patch] {
+                let mut calculator = repo.calculator("v")?.walk_commits()?.force(level);
+                let answer = calculator.compute();
+                assert!(answer.version_number > current, "{} forced", case);
+                let answer = calculator.next_pre_release("rc")?;
+                assert!(answer.version_number > current, "{} forced", case);
+            }
+
+            // first production release
+            if current.major() == 0 {
+                let answer = repo.calculator("v")?.walk_commits()?.promote_first()?;
+                assert!(answer.version_number > current, "{}", case);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn pre_release_before_1_0_0_follows_the_level_reported() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.tag("v0.4.2")?;
+    repo.commit("feat!: replace the interface", &["src/lib.rs"])?;
+
+    let answer = repo
+        .calculator("v")?
+        .walk_commits()?
+        .next_pre_release("rc")?;
+
+    assert_eq!(Level::Minor, answer.bump_level);
+    let current = Semantic::parse("v0.4.2", "v")?;
+    assert_eq!(
+        current.next_pre_release_for(answer.bump_level, "rc")?,
+        answer.version_number
+    );
+    assert_eq!("v0.5.0-rc.1", answer.version_number.to_string());
+    Ok(())
+}
+
+#[test]
+fn baseline_commit_excludes_earlier_commits() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("feat: imported feature", &["README.md"])?;
+    repo.commit("feat!: imported breaking change", &["README.md"])?;
+    let import = repo.commit("chore: import the history", &["README.md"])?;
+    repo.commit("fix: correct a bug", &["src/lib.rs"])?;
+    let current = Semantic::parse("v1.2.3", "v")?;
+
+    let answer = VersionCalculator::with_current_version_in(repo.path(), current.clone())?
+        .walk_commits()?
+        .compute();
+    assert_eq!(Level::Major, answer.bump_level);
+
+    let calculator = VersionCalculator::with_current_version_in(repo.path(), current)?
+        .baseline_commit(&import.to_string()[..7])
+        .walk_commits()?;
+    assert_eq!(1, calculator.commits().len());
+    assert_eq!("correct a bug", calculator.commits()[0].description);
+    let answer = calculator.compute();
+    assert_eq!(Level::Patch, answer.bump_level);
+    assert_eq!("v1.2.4", answer.version_number.to_string());
+    Ok(())
+}
+
+#[test]
+fn all_versions_lists_each_version_once() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.tag("v1.2.3")?;
+    repo.tag("V1.2.3")?;
+    repo.commit("feat: add a feature", &["README.md"])?;
+    repo.tag("V1.3.0")?;
+    repo.tag("v1.3.0")?;
+    repo.tag("v1.3.0-rc.1")?;
+
+    let versions = VersionCalculator::new_ignore_case_in(repo.path(), "v")?.all_versions()?;
+
+    let versions: Vec<String> = versions.iter().map(|v| v.to_string()).collect();
+    assert_eq!(3, versions.len(), "{:?}", versions);
+    assert_eq!("1.2.3", &versions[0][1..]);
+    assert_eq!("1.3.0-rc.1", &versions[1][1..]);
+    assert_eq!("1.3.0", &versions[2][1..]);
+    Ok(())
+}
+
+#[test]
+fn original_tag_keeps_the_tag_as_written() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.tag("V1.2.3")?;
+
+    let calculator = VersionCalculator::new_ignore_case_in(repo.path(), "v")?;
+    assert_eq!(Some("V1.2.3"), calculator.name().original_tag());
+
+    repo.tag("v1.2.4-stable")?;
+    let calculator = VersionCalculator::new_tag_glob_in(repo.path(), &TagGlob::new("v*-stable")?)?;
+    let current = calculator.name();
+    assert_eq!(Some("v1.2.4-stable"), current.original_tag());
+    assert_eq!("v1.2.4", current.to_string());
+
+    repo.commit("fix: correct a bug", &["README.md"])?;
+    let calculator = VersionCalculator::new_tag_glob_in(repo.path(), &TagGlob::new("v*-stable")?)?;
+    let next = calculator.walk_commits()?.compute().version_number;
+    assert_eq!("v1.2.5", next.to_string());
+    assert_eq!(None, next.original_tag());
+    Ok(())
+}
+
+#[test]
+fn pre_release_continues_while_the_core_is_unchanged() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.tag("v1.1.0")?;
+    repo.commit("feat: add a feature", &["src/lib.rs"])?;
+    repo.tag("v1.2.0-rc.3")?;
+    repo.commit("fix: correct a bug", &["src/lib.rs"])?;
+    repo.commit("feat: add another feature", &["src/lib.rs"])?;
+
+    let answer = repo
+        .calculator("v")?
+        .walk_commits()?
+        .next_pre_release("rc")?;
+
+    assert_eq!("v1.2.0-rc.4", answer.version_number.to_string());
+    Ok(())
+}
+
+#[test]
+fn pre_release_starts_a_new_series_when_the_core_changes() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.tag("v1.1.0")?;
+    repo.commit("feat: add a feature", &["src/lib.rs"])?;
+    repo.tag("v1.2.0-rc.3")?;
+    repo.commit("feat!: replace the interface", &["src/lib.rs"])?;
+
+    let answer = repo
+        .calculator("v")?
+        .walk_commits()?
+        .next_pre_release("rc")?;
+
+    assert_eq!(Level::Major, answer.bump_level);
+    assert_eq!("v2.0.0-rc.1", answer.version_number.to_string());
+    Ok(())
+}
+
+#[test]
+fn changelog_lists_the_issues_referenced_in_footers() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.tag("v1.2.3")?;
+    repo.commit(
+        "fix(cli): correct the exit code\n\nCloses #123",
+        &["src/main.rs"],
+    )?;
+
+    let plan = repo.calculator("v")?.plan(PlanOptions::default())?;
+
+    let expected = "\
+## [v1.2.4]
+
+### Bug Fixes
+
+- **cli:** correct the exit code (#123)
+";
+    assert_eq!(expected, plan.changelog.to_string());
+    assert_eq!(
+        vec!["#123".to_string()],
+        plan.changelog.sections[0].entries[0].references
+    );
+    Ok(())
+}
+
+#[test]
+fn distance_and_describe_count_the_commits_since_the_tag() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.tag("v1.2.3")?;
+    for n in 1..=4 {
+        repo.commit(&format!("docs: update the readme {}", n), &["README.md"])?;
+    }
+    let head = repo.commit("not a conventional commit", &["README.md"])?;
+
+    let calculator = repo.calculator("v")?.walk_commits()?;
+
+    assert_eq!(5, calculator.distance());
+    assert_eq!(
+        format!("v1.2.3-5-g{}", &head.to_string()[..7]),
+        calculator.describe(Some(7))?
+    );
+    Ok(())
+}
+
+#[test]
+fn reverts_only_bump_unless_ignored() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["src/lib.rs"])?;
+    repo.tag("v1.2.3")?;
+    repo.commit("revert: add the cache", &["src/lib.rs"])?;
+
+    let calculator = repo.calculator("v")?.walk_commits()?;
+    assert_eq!(Level::Patch, calculator.bump_level());
+
+    let calculator = repo
+        .calculator("v")?
+        .revert_bump(RevertBump::None)
+        .walk_commits()?;
+    assert_eq!(Level::None, calculator.bump_level());
+    Ok(())
+}
+
+#[test]
+fn unknown_types_bump_patch_unless_ignored() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["src/lib.rs"])?;
+    repo.tag("v1.2.3")?;
+    repo.commit("security: rotate the keys", &["src/lib.rs"])?;
+
+    let calculator = repo
+        .calculator("v")?
+        .unknown_type(UnknownType::Patch)
+        .walk_commits()?;
+    assert_eq!(Level::Patch, calculator.bump_level());
+    assert_eq!(Some(TypeHierarchy::Other), calculator.top_level());
+
+    let calculator = repo
+        .calculator("v")?
+        .unknown_type(UnknownType::Ignore)
+        .walk_commits()?;
+    assert_eq!(Level::None, calculator.bump_level());
+    assert_eq!(None, calculator.top_level());
+
+    repo.commit("security!: drop the old keys", &["src/lib.rs"])?;
+    let calculator = repo
+        .calculator("v")?
+        .unknown_type(UnknownType::Ignore)
+        .walk_commits()?;
+    assert_eq!(Level::Major, calculator.bump_level());
+    Ok(())
+}
+
+#[test]
+fn breaking_change_is_reported_whatever_the_bump() -> Result<(), Error> {
+    for (tag, level, version) in [
+        ("v0.4.2", Level::Minor, "v0.5.0"),
+        ("v1.4.2", Level::Major, "v2.0.0"),
+    ] {
+        let repo = TempRepo::new()?;
+        repo.commit("chore: initial commit", &["src/lib.rs"])?;
+        repo.tag(tag)?;
+        repo.commit("feat!: change the api", &["src/lib.rs"])?;
+
+        let answer = repo.calculator("v")?.walk_commits()?.next_version();
+
+        assert_eq!(level, answer.bump_level, "{}", tag);
+        assert_eq!(version, answer.version_number.to_string(), "{}", tag);
+        assert!(answer.is_breaking_change(), "{}", tag);
+        let current = Semantic::parse(tag, "v")?;
+        assert!(answer.bump_level.is_breaking_from(&current), "{}", tag);
+    }
+
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["src/lib.rs"])?;
+    repo.tag("v0.4.2")?;
+    repo.commit("feat: add a feature", &["src/lib.rs"])?;
+    let answer = repo.calculator("v")?.walk_commits()?.next_version();
+    assert_eq!(Level::Patch, answer.bump_level);
+    assert!(!answer.is_breaking_change());
+    Ok(())
+}
+
+#[test]
+fn perf_commits_bump_minor_at_the_feature_level() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["src/lib.rs"])?;
+    repo.tag("v1.2.3")?;
+    repo.commit("perf: cache the parsed tags", &["src/lib.rs"])?;
+    repo.commit("perf(walk): skip the merged commits", &["src/lib.rs"])?;
+
+    let calculator = repo.calculator("v")?.walk_commits()?;
+    assert_eq!(Level::Patch, calculator.bump_level());
+    assert_eq!(Some(TypeHierarchy::Other), calculator.top_level());
+
+    let calculator = repo
+        .calculator("v")?
+        .type_level("perf", TypeHierarchy::Feature)
+        .walk_commits()?;
+    assert_eq!(Level::Minor, calculator.bump_level());
+    assert_eq!(Some(TypeHierarchy::Feature), calculator.top_level());
+    assert_eq!("v1.3.0", calculator.compute().version_number.to_string());
+    Ok(())
+}
+
+#[test]
+fn tag_template_names_the_tag_created_and_reads_it_back() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.tag("release/v1.2.3")?;
+    repo.commit("feat: add a feature", &["src/lib.rs"])?;
+    let template = TagTemplate::new("release/{prefix}{version}", "v")?;
+
+    let mut calculator =
+        VersionCalculator::new_tag_template_in(repo.path(), &template)?.walk_commits()?;
+    let answer = calculator.next_version();
+    calculator.create_tag(&answer.version_number)?;
+
+    assert!(repo
+        .repo()
+        .refname_to_id("refs/tags/release/v1.3.0")
+        .is_ok());
+    repo.commit("fix: correct a bug", &["src/lib.rs"])?;
+    let calculator =
+        VersionCalculator::new_tag_template_in(repo.path(), &template)?.walk_commits()?;
+    assert_eq!("release/v1.3.0", calculator.baseline_tag());
+    assert_eq!(1, calculator.distance());
+    assert_eq!(
+        "release/v1.3.1",
+        calculator.compute().version_number.to_string()
+    );
+    Ok(())
+}
+
+#[test]
+fn skip_marker_excludes_the_commit() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["src/lib.rs"])?;
+    repo.tag("v1.2.3")?;
+    repo.commit("feat: add a feature\n\n[skip release]", &["src/lib.rs"])?;
+
+    let calculator = repo.calculator("v")?.walk_commits()?;
+    assert_eq!(Level::None, calculator.bump_level());
+    assert_eq!(1, calculator.distance());
+
+    let calculator = repo
+        .calculator("v")?
+        .skip_markers(vec!["[skip ci]".to_string()])
+        .walk_commits()?;
+    assert_eq!(Level::Minor, calculator.bump_level());
+    Ok(())
+}
+
+#[test]
+fn tag_glob_finds_version_tags_after_a_path() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.tag("release/v1.2.3")?;
+    repo.tag("v9.0.0")?;
+    repo.commit("feat: add a feature", &["src/lib.rs"])?;
+
+    let glob = TagGlob::new("release/v*")?;
+    let mut calculator = VersionCalculator::new_tag_glob_in(repo.path(), &glob)?.walk_commits()?;
+
+    assert_eq!("release/v1.2.3", calculator.baseline_tag());
+    assert_eq!(1, calculator.distance());
+    assert_eq!(
+        "release/v1.3.0",
+        calculator.next_version().version_number.to_string()
+    );
+    Ok(())
+}
+
+#[test]
+fn tag_glob_matches_a_suffix_after_the_version() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.tag("v1.2.3-stable")?;
+    repo.commit("fix: correct a bug", &["src/lib.rs"])?;
+    repo.tag("v1.2.4")?;
+    repo.commit("feat: add a feature", &["src/lib.rs"])?;
+
+    let glob = TagGlob::new("v*-stable")?;
+    let mut calculator = VersionCalculator::new_tag_glob_in(repo.path(), &glob)?.walk_commits()?;
+
+    assert_eq!("v1.2.3-stable", calculator.baseline_tag());
+    assert_eq!(2, calculator.distance());
+    assert_eq!(
+        "v1.3.0",
+        calculator.next_version().version_number.to_string()
+    );
+    Ok(())
+}
+
+#[test]
+fn counts_reports_every_type() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.tag("v1.2.3")?;
+
+    assert!(repo.calculator("v")?.counts().is_empty());
+
+    repo.commit("feat: add a feature", &["src/lib.rs"])?;
+    repo.commit("fix: correct a bug", &["src/lib.rs"])?;
+    repo.commit("fix(cli): correct the exit code", &["src/main.rs"])?;
+    repo.commit("docs: update the readme", &["README.md"])?;
+    repo.commit("update the changes", &["CHANGES.md"])?;
+
+    let calculator = repo.calculator("v")?.walk_commits()?;
+
+    let expected: HashMap<String, u32> = [("feat", 1), ("fix", 2), ("docs", 1)]
+        .iter()
+        .map(|(type_, count)| (type_.to_string(), *count))
+        .collect();
+    assert_eq!(expected, calculator.counts());
+    Ok(())
+}
+
+#[test]
+fn numbered_pre_release_uses_the_counter_given() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.tag("v1.2.0")?;
+    repo.tag("v1.3.0-ci.9")?;
+    repo.commit("feat: add a feature", &["src/lib.rs"])?;
+
+    let mut calculator = repo.calculator("v")?.walk_commits()?;
+    let answer = calculator.next_pre_release_numbered("ci", "427")?;
+    assert_eq!("v1.3.0-ci.427", answer.version_number.to_string());
+
+    let result = calculator.next_pre_release_numbered("ci", "0427");
+    assert!(matches!(result, Err(Error::InvalidPreRelease(_))));
+    Ok(())
+}
+
+#[test]
+fn audit_flags_the_inconsistent_tags() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.tag("v1.0.0")?;
+    repo.commit("fix: correct a bug", &["src/lib.rs"])?;
+    repo.tag("v1.0.1-rc.1")?;
+    repo.commit("feat!: replace the api", &["src/lib.rs"])?;
+    repo.tag("v2.0.0")?;
+    repo.commit("feat: add a feature", &["src/lib.rs"])?;
+    repo.tag("v1.2.0")?;
+    repo.tag("v1.2")?;
+    repo.tag("v2.0.1-rc.1")?;
+
+    let audit = VersionCalculator::audit_tags_in(repo.path(), "v", PreReleaseStyle::SemVer, false)?;
+
+    let version = |tag| Semantic::parse(tag, "v").unwrap();
+    assert_eq!(5, audit.versions);
+    assert!(!audit.is_consistent());
+    assert_eq!(4, audit.issues.len(), "{}", audit);
+    assert!(matches!(&audit.issues[0], TagIssue::Unparsed(name, _) if name == "v1.2"));
+    assert_eq!(
+        TagIssue::Gap(version("v1.0.0"), version("v1.2.0")),
+        audit.issues[1]
+    );
+    assert_eq!(
+        TagIssue::OutOfOrder(version("v1.2.0"), version("v2.0.0")),
+        audit.issues[2]
+    );
+    assert_eq!(
+        TagIssue::NotPromoted(version("v1.0.1-rc.1")),
+        audit.issues[3]
+    );
+    Ok(())
+}
+
+#[test]
+fn audit_accepts_a_consistent_history() -> Result<(), Error> {
+    let repo = TempRepo::new()?;
+    repo.commit("chore: initial commit", &["README.md"])?;
+    repo.tag("v1.0.0")?;
+    repo.commit("feat: add a feature", &["src/lib.rs"])?;
+    repo.tag("v1.1.0-rc.1")?;
+    repo.tag("v1.1.0")?;
+    repo.commit("fix: correct a bug", &["src/lib.rs"])?;
+    repo.tag("v1.1.1")?;
+    repo.commit("feat!: replace the api", &["src/lib.rs"])?;
+    repo.tag("v2.0.0-rc.1")?;
+
+    let audit = VersionCalculator::audit_tags_in(repo.path(), "v", PreReleaseStyle::SemVer, false)?;
+
+    assert!(audit.is_consistent(), "{}", audit);
+    assert_eq!(5, audit.versions);
+    Ok(())
+}
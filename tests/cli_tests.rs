@@ -1,6 +1,1457 @@
+use std::fs;
+use std::process::{Command, Output};
+
+use nextsv::test_util::TempRepo;
+
 #[test]
 fn cli_tests() {
     trycmd::TestCases::new()
         .case("tests/cmd/*.trycmd")
         .case("README.md");
 }
+
+fn nextsv(repo: &TempRepo, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_nextsv"))
+        .current_dir(repo.path())
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[test]
+fn required_files_enforced_on_docs_only_change_at_other_level() {
+    let repo = TempRepo::new().unwrap();
+    repo.commit("chore: initial commit", &["README.md", "CHANGES.md"])
+        .unwrap();
+    repo.tag("v1.0.0").unwrap();
+    repo.commit("docs: update the readme", &["README.md"])
+        .unwrap();
+
+    let output = nextsv(
+        &repo,
+        &["-e", "other", "-r", "README.md", "-r", "CHANGES.md"],
+    );
+
+    assert_eq!(Some(13), output.status.code());
+    assert_eq!(
+        "Missing the required file(s): [\"CHANGES.md\"]. Enforcing at other level because the top type found is other.\n",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn required_files_missing_reports_the_level_enforced() {
+    let repo = TempRepo::new().unwrap();
+    repo.commit("chore: initial commit", &["README.md", "CHANGES.md"])
+        .unwrap();
+    repo.tag("v1.0.0").unwrap();
+    repo.commit("feat: add a feature", &["README.md"]).unwrap();
+
+    let output = nextsv(&repo, &["-e", "feature", "-r", "CHANGES.md"]);
+
+    assert_eq!(Some(13), output.status.code());
+    assert_eq!(
+        "Missing the required file(s): [\"CHANGES.md\"]. Enforcing at feature level because the top type found is feature.\n",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn require_file_adds_to_the_required_files() {
+    let repo = TempRepo::new().unwrap();
+    repo.commit(
+        "chore: initial commit",
+        &["README.md", "CHANGES.md", "Cargo.toml"],
+    )
+    .unwrap();
+    repo.tag("v1.0.0").unwrap();
+    repo.commit("feat: add a feature", &["README.md"]).unwrap();
+    std::fs::write(
+        repo.path().join("release.require"),
+        "# files updated for every release\n\nCHANGES.md\n  # the manifest\n  Cargo.toml  \n\n",
+    )
+    .unwrap();
+
+    let output = nextsv(
+        &repo,
+        &["--require-file", "release.require", "-r", "README.md"],
+    );
+
+    assert_eq!(Some(13), output.status.code());
+    assert_eq!(
+        "Missing the required file(s): [\"CHANGES.md\", \"Cargo.toml\"]. Enforcing at feature level because the top type found is feature.\n",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn required_files_met_on_docs_only_change_at_other_level() {
+    let repo = TempRepo::new().unwrap();
+    repo.commit("chore: initial commit", &["README.md", "CHANGES.md"])
+        .unwrap();
+    repo.tag("v1.0.0").unwrap();
+    repo.commit("docs: update the readme", &["README.md", "CHANGES.md"])
+        .unwrap();
+
+    let output = nextsv(
+        &repo,
+        &[
+            "--level",
+            "-e",
+            "other",
+            "-r",
+            "README.md",
+            "-r",
+            "CHANGES.md",
+        ],
+    );
+
+    assert!(output.status.success());
+    assert_eq!("patch\n", stdout(&output));
+}
+
+#[test]
+fn required_files_not_enforced_on_docs_only_change_at_feature_level() {
+    let repo = TempRepo::new().unwrap();
+    repo.commit("chore: initial commit", &["README.md", "CHANGES.md"])
+        .unwrap();
+    repo.tag("v1.0.0").unwrap();
+    repo.commit("docs: update the readme", &["README.md"])
+        .unwrap();
+
+    let output = nextsv(&repo, &["--level", "-r", "CHANGES.md"]);
+
+    assert!(output.status.success());
+    assert_eq!("patch\n", stdout(&output));
+}
+
+#[test]
+fn required_files_not_enforced_without_conventional_commits() {
+    let repo = TempRepo::new().unwrap();
+    repo.commit("chore: initial commit", &["README.md", "CHANGES.md"])
+        .unwrap();
+    repo.tag("v1.0.0").unwrap();
+    repo.commit("update the readme", &["README.md"]).unwrap();
+
+    let output = nextsv(&repo, &["--level", "-e", "other", "-r", "CHANGES.md"]);
+
+    assert!(output.status.success());
+    assert_eq!("none\n", stdout(&output));
+}
+
+#[test]
+fn no_conventional_commits_is_reported() {
+    let repo = TempRepo::new().unwrap();
+    repo.commit("chore: initial commit", &["README.md"])
+        .unwrap();
+    repo.tag("v1.0.0").unwrap();
+    repo.commit("update the readme", &["README.md"]).unwrap();
+    repo.commit("Fix the typo", &["README.md"]).unwrap();
+
+    let output = nextsv(&repo, &["--level"]);
+
+    assert!(output.status.success());
+    assert_eq!("none\n", stdout(&output));
+    assert_eq!(
+        "warning: None of the 2 commit(s) since v1.0.0 are conventional commits\n",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let output = nextsv(&repo, &["--level", "--fail-on-no-conventional"]);
+
+    assert_eq!(Some(10), output.status.code());
+    assert_eq!("", stdout(&output));
+    assert_eq!(
+        "None of the 2 commit(s) since v1.0.0 are conventional commits\n",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn note_bump_major_overrides_patch_commit() {
+    let repo = TempRepo::new().unwrap();
+    repo.commit("chore: initial commit", &["README.md"])
+        .unwrap();
+    repo.tag("v1.0.0").unwrap();
+    let id = repo
+        .commit("fix: correct the readme", &["README.md"])
+        .unwrap();
+    repo.note("refs/notes/nextsv", id, "bump: major\n").unwrap();
+
+    let output = nextsv(&repo, &["--number", "--read-notes", "refs/notes/nextsv"]);
+
+    assert!(output.status.success());
+    assert_eq!("v2.0.0\n", stdout(&output));
+}
+
+#[test]
+fn notes_ignored_unless_read() {
+    let repo = TempRepo::new().unwrap();
+    repo.commit("chore: initial commit", &["README.md"])
+        .unwrap();
+    repo.tag("v1.0.0").unwrap();
+    let id = repo
+        .commit("fix: correct the readme", &["README.md"])
+        .unwrap();
+    repo.note("refs/notes/nextsv", id, "bump: major\n").unwrap();
+
+    let output = nextsv(&repo, &["--number"]);
+
+    assert!(output.status.success());
+    assert_eq!("v1.0.1\n", stdout(&output));
+}
+
+#[test]
+fn note_skip_excludes_commit() {
+    let repo = TempRepo::new().unwrap();
+    repo.commit("chore: initial commit", &["README.md"])
+        .unwrap();
+    repo.tag("v1.0.0").unwrap();
+    repo.commit("fix: correct the readme", &["README.md"])
+        .unwrap();
+    let id = repo.commit("feat: add a feature", &["README.md"]).unwrap();
+    repo.note("refs/notes/nextsv", id, "skip: true\n").unwrap();
+
+    let output = nextsv(&repo, &["--number", "--read-notes", "nextsv"]);
+
+    assert!(output.status.success());
+    assert_eq!("v1.0.1\n", stdout(&output));
+}
+
+#[test]
+fn env_file_written_for_minor_bump() {
+    let repo = TempRepo::new().unwrap();
+    repo.commit("chore: initial commit", &["README.md"])
+        .unwrap();
+    repo.tag("v1.2.3").unwrap();
+    repo.commit("feat: add a feature", &["README.md"]).unwrap();
+    let env_file = repo.path().join("nextsv.env");
+
+    let output = nextsv(&repo, &["--env-file", env_file.to_str().unwrap()]);
+
+    assert!(output.status.success());
+    assert_eq!(
+        "NEXTSV_VERSION=v1.3.0\nNEXTSV_LEVEL=minor\n",
+        fs::read_to_string(&env_file).unwrap()
+    );
+}
+
+#[test]
+fn env_file_appended() {
+    let repo = TempRepo::new().unwrap();
+    repo.commit("chore: initial commit", &["README.md"])
+        .unwrap();
+    repo.tag("v1.2.3").unwrap();
+    repo.commit("fix: correct a bug", &["README.md"]).unwrap();
+    let env_file = repo.path().join("nextsv.env");
+    fs::write(&env_file, "EXISTING=true\n").unwrap();
+
+    let output = nextsv(
+        &repo,
+        &[
+            "--env-file",
+            env_file.to_str().unwrap(),
+            "--env-file-mode",
+            "append",
+        ],
+    );
+
+    assert!(output.status.success());
+    assert_eq!(
+        "EXISTING=true\nNEXTSV_VERSION=v1.2.4\nNEXTSV_LEVEL=patch\n",
+        fs::read_to_string(&env_file).unwrap()
+    );
+}
+
+#[test]
+fn explain_reports_baseline_tag_for_multi_tag_repo() {
+    let repo = TempRepo::new().unwrap();
+    repo.commit("chore: initial commit", &["README.md"])
+        .unwrap();
+    repo.tag("v1.0.0").unwrap();
+    repo.commit("feat: add a feature", &["README.md"]).unwrap();
+    repo.tag("v1.9.0").unwrap();
+    let baseline = repo
+        .commit("feat: add another feature", &["README.md"])
+        .unwrap();
+    repo.tag("v1.10.0").unwrap();
+    repo.tag("other-2.0.0").unwrap();
+    repo.commit("fix: correct a bug", &["README.md"]).unwrap();
+
+    let output = nextsv(&repo, &["--level", "--explain"]);
+
+    assert!(output.status.success());
+    assert_eq!("patch\n", stdout(&output));
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    assert!(stderr.contains(&format!("baseline tag: refs/tags/v1.10.0 ({})\n", baseline)));
+}
+
+#[test]
+fn promote_if_breaking_with_breaking_change() {
+    let repo = TempRepo::new().unwrap();
+    repo.commit("chore: initial commit", &["README.md"])
+        .unwrap();
+    repo.tag("v0.7.9").unwrap();
+    repo.commit("feat!: replace the interface", &["README.md"])
+        .unwrap();
+
+    let output = nextsv(&repo, &["--number", "--promote-if-breaking"]);
+
+    assert!(output.status.success());
+    assert_eq!("v1.0.0\n", stdout(&output));
+}
+
+#[test]
+fn promote_if_breaking_without_breaking_change() {
+    let repo = TempRepo::new().unwrap();
+    repo.commit("chore: initial commit", &["README.md"])
+        .unwrap();
+    repo.tag("v0.7.9").unwrap();
+    repo.commit("feat: add a feature", &["README.md"]).unwrap();
+
+    let output = nextsv(&repo, &["--number", "--promote-if-breaking"]);
+
+    assert!(output.status.success());
+    assert_eq!("v0.7.10\n", stdout(&output));
+}
+
+#[test]
+fn promote_if_breaking_rejects_production_version() {
+    let repo = TempRepo::new().unwrap();
+    repo.commit("chore: initial commit", &["README.md"])
+        .unwrap();
+    repo.tag("v1.2.0").unwrap();
+    repo.commit("fix: correct a bug", &["README.md"]).unwrap();
+
+    let output = nextsv(&repo, &["--promote-if-breaking"]);
+
+    assert!(!output.status.success());
+}
+
+#[test]
+fn prefix_read_from_git_config() {
+    let repo = TempRepo::new().unwrap();
+    repo.repo()
+        .config()
+        .unwrap()
+        .set_str("nextsv.tagPrefix", "release-")
+        .unwrap();
+    repo.commit("chore: initial commit", &["README.md"])
+        .unwrap();
+    repo.tag("v2.0.0").unwrap();
+    repo.tag("release-1.0.0").unwrap();
+    repo.commit("feat: add a feature", &["README.md"]).unwrap();
+
+    let output = nextsv(&repo, &["--number"]);
+
+    assert!(output.status.success());
+    assert_eq!("release-1.1.0\n", stdout(&output));
+}
+
+#[test]
+fn prefix_option_overrides_git_config() {
+    let repo = TempRepo::new().unwrap();
+    repo.repo()
+        .config()
+        .unwrap()
+        .set_str("nextsv.tagPrefix", "release-")
+        .unwrap();
+    repo.commit("chore: initial commit", &["README.md"])
+        .unwrap();
+    repo.tag("v2.0.0").unwrap();
+    repo.tag("release-1.0.0").unwrap();
+    repo.commit("feat: add a feature", &["README.md"]).unwrap();
+
+    let output = nextsv(&repo, &["--number", "--prefix", "v"]);
+
+    assert!(output.status.success());
+    assert_eq!("v2.1.0\n", stdout(&output));
+}
+
+fn minor_fixture() -> TempRepo {
+    let repo = TempRepo::new().unwrap();
+    repo.commit("chore: initial commit", &["README.md"])
+        .unwrap();
+    repo.tag("v1.2.3").unwrap();
+    repo.commit("feat: add a feature", &["README.md"]).unwrap();
+    repo
+}
+
+#[test]
+fn output_defaults_to_version_number() {
+    let repo = minor_fixture();
+
+    let output = nextsv(&repo, &[]);
+
+    assert!(output.status.success());
+    assert_eq!("v1.3.0\n", stdout(&output));
+}
+
+#[test]
+fn output_level_only() {
+    let repo = minor_fixture();
+
+    for flag in ["--level", "--print-level-only"] {
+        let output = nextsv(&repo, &[flag]);
+
+        assert!(output.status.success());
+        assert_eq!("minor\n", stdout(&output), "{}", flag);
+    }
+}
+
+#[test]
+fn output_version_only() {
+    let repo = minor_fixture();
+
+    for flag in ["--number", "--print-version-only"] {
+        let output = nextsv(&repo, &[flag]);
+
+        assert!(output.status.success());
+        assert_eq!("v1.3.0\n", stdout(&output), "{}", flag);
+    }
+}
+
+#[test]
+fn output_version_and_level_labelled() {
+    let repo = minor_fixture();
+
+    let output = nextsv(&repo, &["--number", "--level"]);
+
+    assert!(output.status.success());
+    assert_eq!("version: v1.3.0\nlevel: minor\n", stdout(&output));
+}
+
+#[test]
+fn output_only_selectors_are_exclusive() {
+    let repo = minor_fixture();
+
+    let output = nextsv(&repo, &["--print-version-only", "--print-level-only"]);
+    assert!(!output.status.success());
+
+    let output = nextsv(&repo, &["--print-version-only", "--level"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn exit_code_no_change_honoured() {
+    let repo = TempRepo::new().unwrap();
+    repo.commit("chore: initial commit", &["README.md"])
+        .unwrap();
+    repo.tag("v1.2.3").unwrap();
+    repo.commit("update the readme", &["README.md"]).unwrap();
+
+    let output = nextsv(&repo, &["--exit-code-no-change", "3"]);
+    assert_eq!(Some(3), output.status.code());
+    assert_eq!("v1.2.3\n", stdout(&output));
+
+    let output = nextsv(&repo, &[]);
+    assert_eq!(Some(0), output.status.code());
+}
+
+#[test]
+fn exit_code_missing_required_remapped() {
+    let repo = minor_fixture();
+
+    let output = nextsv(
+        &repo,
+        &["-r", "CHANGES.md", "--exit-code-missing-required", "1"],
+    );
+
+    assert_eq!(Some(1), output.status.code());
+}
+
+#[test]
+fn base_ref_reports_the_branch_bump() {
+    let repo = minor_fixture();
+    repo.branch("main").unwrap();
+    repo.commit("fix: correct a bug on the branch", &["src/lib.rs"])
+        .unwrap();
+
+    let output = nextsv(&repo, &["--base-ref", "main"]);
+
+    assert!(output.status.success());
+    assert_eq!("v1.2.4\n", stdout(&output));
+}
+
+#[test]
+fn pre_release_starts_series_on_the_next_version() {
+    let repo = minor_fixture();
+
+    let output = nextsv(&repo, &["--pre-release", "rc"]);
+
+    assert!(output.status.success());
+    assert_eq!("v1.3.0-rc.1\n", stdout(&output));
+}
+
+#[test]
+fn ignore_types_excludes_commits_of_the_type() {
+    let repo = minor_fixture();
+    repo.commit("chore: tidy up", &["README.md"]).unwrap();
+
+    let output = nextsv(&repo, &["--ignore-types", "feat,chore"]);
+
+    assert_eq!(Some(0), output.status.code());
+    assert_eq!("v1.2.3\n", stdout(&output));
+}
+
+#[test]
+fn ignore_types_rejects_unknown_type() {
+    let repo = minor_fixture();
+
+    let output = nextsv(&repo, &["--ignore-types", "feat,fx"]);
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unknown type"));
+}
+
+#[test]
+fn type_level_sets_the_level_of_perf_commits() {
+    let repo = TempRepo::new().unwrap();
+    repo.commit("chore: initial commit", &["README.md"])
+        .unwrap();
+    repo.tag("v1.2.3").unwrap();
+    repo.commit("perf: speed up the walk", &["README.md"])
+        .unwrap();
+
+    let output = nextsv(&repo, &["--type-level", "perf=feature"]);
+    assert!(output.status.success());
+    assert_eq!("v1.3.0\n", stdout(&output));
+
+    let output = nextsv(&repo, &["--type-level", "perf=breaking"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("breaking level"));
+}
+
+#[test]
+fn require_only_on_release_skips_check_without_release() {
+    let repo = TempRepo::new().unwrap();
+    repo.commit("chore: initial commit", &["README.md"])
+        .unwrap();
+    repo.tag("v1.2.3").unwrap();
+    repo.commit("update the readme", &["README.md"]).unwrap();
+
+    let output = nextsv(
+        &repo,
+        &[
+            "-r",
+            "CHANGES.md",
+            "--enforce-level",
+            "other",
+            "--require-only-on-release",
+        ],
+    );
+
+    assert!(output.status.success());
+    assert_eq!("v1.2.3\n", stdout(&output));
+}
+
+#[test]
+fn require_only_on_release_enforces_check_for_release() {
+    let repo = minor_fixture();
+
+    let output = nextsv(&repo, &["-r", "CHANGES.md", "--require-only-on-release"]);
+
+    assert_eq!(Some(13), output.status.code());
+}
+
+#[test]
+fn require_only_on_release_enforces_check_for_forced_release() {
+    let repo = TempRepo::new().unwrap();
+    repo.commit("chore: initial commit", &["README.md"])
+        .unwrap();
+    repo.tag("v1.2.3").unwrap();
+    repo.commit("update the readme", &["README.md"]).unwrap();
+
+    let output = nextsv(
+        &repo,
+        &[
+            "-r",
+            "CHANGES.md",
+            "--enforce-level",
+            "other",
+            "--require-only-on-release",
+            "--force",
+            "patch",
+        ],
+    );
+
+    assert_eq!(Some(13), output.status.code());
+    assert_eq!("", stdout(&output));
+}
+
+#[test]
+fn update_state_writes_the_next_version() {
+    let repo = TempRepo::new().unwrap();
+    repo.commit("feat: add a feature", &["README.md"]).unwrap();
+    let path = repo.path().join(".nextsv-state.json");
+    std::fs::write(&path, r#"{"version": "1.2.3", "prefix": "v"}"#).unwrap();
+
+    let output = nextsv(
+        &repo,
+        &["--state-file", ".nextsv-state.json", "--update-state"],
+    );
+
+    assert!(output.status.success());
+    assert_eq!("v1.3.0\n", stdout(&output));
+    let state = std::fs::read_to_string(&path).unwrap();
+    assert!(state.contains(r#""version": "1.3.0""#), "{}", state);
+}
+
+#[test]
+fn tag_updates_the_state_file() {
+    let repo = TempRepo::new().unwrap();
+    repo.commit("feat: add a feature", &["README.md"]).unwrap();
+    let path = repo.path().join(".nextsv-state.json");
+    std::fs::write(&path, r#"{"version": "1.2.3", "prefix": "v"}"#).unwrap();
+
+    let output = nextsv(
+        &repo,
+        &["--state-file", ".nextsv-state.json", "--tag", "--yes"],
+    );
+
+    assert!(output.status.success());
+    assert_eq!("v1.3.0\n", stdout(&output));
+    assert!(repo.repo().revparse_single("refs/tags/v1.3.0").is_ok());
+    let state = std::fs::read_to_string(&path).unwrap();
+    assert!(state.contains(r#""version": "1.3.0""#), "{}", state);
+}
+
+#[test]
+fn verify_fails_when_latest_tag_is_wrong() {
+    let repo = minor_fixture();
+    repo.tag("v1.2.4").unwrap();
+
+    let output = nextsv(&repo, &["--verify"]);
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("Latest version tag v1.2.4 does not match the version calculated from its commits: v1.3.0 (minor change)"));
+}
+
+#[test]
+fn verify_passes_when_latest_tag_is_right() {
+    let repo = minor_fixture();
+    repo.tag("v1.3.0").unwrap();
+
+    let output = nextsv(&repo, &["--verify"]);
+
+    assert!(output.status.success());
+    assert_eq!("v1.3.0\n", stdout(&output));
+}
+
+#[test]
+fn pre_release_style_reads_and_writes_the_style() {
+    let repo = minor_fixture();
+
+    let output = nextsv(
+        &repo,
+        &["--pre-release-style", "dot-suffix", "--pre-release", "rc"],
+    );
+    assert!(output.status.success());
+    assert_eq!("v1.3.0.rc1\n", stdout(&output));
+
+    repo.tag("v1.3.0.rc1").unwrap();
+    let output = nextsv(&repo, &["--pre-release-style", "dot-suffix"]);
+    assert!(output.status.success());
+    assert_eq!("v1.3.0.rc1\n", stdout(&output));
+}
+
+#[test]
+fn build_hash_uses_the_abbreviation() {
+    let repo = minor_fixture();
+    let head = repo.repo().head().unwrap().target().unwrap().to_string();
+
+    let output = nextsv(&repo, &["--build-hash", "--abbrev", "12"]);
+
+    assert!(output.status.success());
+    assert_eq!(format!("v1.3.0+{}\n", &head[..12]), stdout(&output));
+}
+
+#[test]
+fn abbrev_must_be_between_4_and_40() {
+    let repo = minor_fixture();
+
+    let output = nextsv(&repo, &["--build-hash", "--abbrev", "3"]);
+
+    assert!(!output.status.success());
+}
+
+#[test]
+fn output_bump_only() {
+    let repo = minor_fixture();
+
+    let output = nextsv(&repo, &["--bump-only"]);
+
+    assert!(output.status.success());
+    assert_eq!(b"minor\n".to_vec(), output.stdout);
+}
+
+#[test]
+fn output_without_newline() {
+    let repo = minor_fixture();
+
+    let cases: [(&[&str], &[u8]); 4] = [
+        (&["--no-newline"], b"v1.3.0"),
+        (&["--bump-only", "--no-newline"], b"minor"),
+        (&["--print-version-only", "--no-newline"], b"v1.3.0"),
+        (
+            &["--number", "--level", "--no-newline"],
+            b"version: v1.3.0\nlevel: minor",
+        ),
+    ];
+    for (args, expected) in cases {
+        let output = nextsv(&repo, args);
+
+        assert!(output.status.success());
+        assert_eq!(expected.to_vec(), output.stdout, "{:?}", args);
+    }
+}
+
+#[test]
+fn deps_bump_controls_dependency_updates() {
+    let repo = TempRepo::new().unwrap();
+    repo.commit("chore: initial commit", &["Cargo.toml"])
+        .unwrap();
+    repo.tag("v1.2.3").unwrap();
+    repo.commit("chore(deps): bump serde to 1.0.150", &["Cargo.toml"])
+        .unwrap();
+    repo.commit("chore(deps): bump clap to 4.0.32", &["Cargo.toml"])
+        .unwrap();
+
+    let cases: [(&[&str], &str); 3] = [
+        (&[], "v1.2.4\n"),
+        (&["--deps-bump", "patch"], "v1.2.4\n"),
+        (&["--deps-bump", "none"], "v1.2.3\n"),
+    ];
+    for (args, expected) in cases {
+        let output = nextsv(&repo, args);
+
+        assert!(output.status.success());
+        assert_eq!(expected, stdout(&output), "{:?}", args);
+    }
+}
+
+#[test]
+fn revert_bump_controls_revert_commits() {
+    let repo = TempRepo::new().unwrap();
+    repo.commit("chore: initial commit", &["src/lib.rs"])
+        .unwrap();
+    repo.tag("v1.2.3").unwrap();
+    repo.commit("revert: add the cache", &["src/lib.rs"])
+        .unwrap();
+    repo.commit("revert: replace the parser", &["src/lib.rs"])
+        .unwrap();
+
+    let cases: [(&[&str], &str); 3] = [
+        (&[], "v1.2.4\n"),
+        (&["--revert-bump", "fix"], "v1.2.4\n"),
+        (&["--revert-bump", "none"], "v1.2.3\n"),
+    ];
+    for (args, expected) in cases {
+        let output = nextsv(&repo, args);
+
+        assert!(output.status.success());
+        assert_eq!(expected, stdout(&output), "{:?}", args);
+    }
+}
+
+#[test]
+fn min_feats_for_minor_counts_fewer_features_as_patch() {
+    let repo = minor_fixture();
+
+    let output = nextsv(&repo, &["--min-feats-for-minor", "2"]);
+    assert!(output.status.success());
+    assert_eq!("v1.2.4\n", stdout(&output));
+
+    repo.commit("feat: add another feature", &["README.md"])
+        .unwrap();
+    let output = nextsv(&repo, &["--min-feats-for-minor", "2"]);
+    assert!(output.status.success());
+    assert_eq!("v1.3.0\n", stdout(&output));
+}
+
+#[test]
+fn force_options_set_the_next_version() {
+    let cases = [
+        ("v1.4.2", "major", "v2.0.0\n"),
+        ("v1.4.2", "minor", "v1.5.0\n"),
+        ("v1.4.2", "patch", "v1.4.3\n"),
+        ("v0.4.2", "first", "v1.0.0\n"),
+    ];
+    for (tag, option, expected) in cases {
+        let repo = TempRepo::new().unwrap();
+        repo.commit("chore: initial commit", &["README.md"])
+            .unwrap();
+        repo.tag(tag).unwrap();
+        repo.commit("docs: update the readme", &["README.md"])
+            .unwrap();
+
+        let output = nextsv(&repo, &["--force", option]);
+
+        assert!(output.status.success(), "{}", option);
+        assert_eq!(expected, stdout(&output), "{}", option);
+    }
+}
+
+#[test]
+fn commitlint_config_limits_the_types_counted() {
+    let repo = TempRepo::new().unwrap();
+    repo.commit("chore: initial commit", &["README.md"])
+        .unwrap();
+    repo.tag("v1.2.3").unwrap();
+    repo.commit("chore: tidy the build", &["README.md"])
+        .unwrap();
+    std::fs::write(
+        repo.path().join(".commitlintrc.json"),
+        r#"{"rules": {"type-enum": [2, "always", ["feat", "fix"]]}}"#,
+    )
+    .unwrap();
+
+    let output = nextsv(&repo, &[]);
+    assert_eq!("v1.2.4\n", stdout(&output));
+
+    let output = nextsv(&repo, &["--commitlint-config"]);
+    assert!(output.status.success());
+    assert_eq!("v1.2.3\n", stdout(&output));
+
+    repo.commit("fix: correct a bug", &["README.md"]).unwrap();
+    let output = nextsv(&repo, &["--commitlint-config", ".commitlintrc.json"]);
+    assert_eq!("v1.2.4\n", stdout(&output));
+
+    let output = nextsv(&repo, &["--commitlint-config", "missing.json"]);
+    assert!(output.status.success());
+    assert_eq!("v1.2.4\n", stdout(&output));
+}
+
+#[test]
+fn prefix_case_insensitive_finds_uppercase_tags() {
+    let repo = TempRepo::new().unwrap();
+    repo.commit("chore: initial commit", &["README.md"])
+        .unwrap();
+    repo.tag("V1.2.3").unwrap();
+    repo.commit("feat: add a feature", &["README.md"]).unwrap();
+
+    let output = nextsv(&repo, &[]);
+    assert!(!output.status.success());
+
+    let output = nextsv(&repo, &["--prefix-case-insensitive", "--summary"]);
+    assert!(output.status.success());
+    assert_eq!("V1.3.0\n", stdout(&output));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("1 commit since V1.2.3"), "{}", stderr);
+}
+
+#[test]
+fn level_from_file_forces_the_change() {
+    let repo = TempRepo::new().unwrap();
+    repo.commit("chore: initial commit", &["README.md"])
+        .unwrap();
+    repo.tag("v1.2.3").unwrap();
+    repo.commit("docs: update the readme", &["README.md"])
+        .unwrap();
+    std::fs::write(repo.path().join("level.txt"), "minor\n").unwrap();
+    std::fs::write(repo.path().join("bad.txt"), "feature\n").unwrap();
+
+    let output = nextsv(&repo, &["--level-from", "level.txt"]);
+    assert!(output.status.success());
+    assert_eq!("v1.3.0\n", stdout(&output));
+
+    let output = nextsv(&repo, &["--level-from", "bad.txt"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("feature is not a valid level name"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn tags_lists_how_each_tag_parsed() {
+    let repo = TempRepo::new().unwrap();
+    repo.commit("chore: initial commit", &["README.md"])
+        .unwrap();
+    repo.tag("v1.2.3").unwrap();
+    repo.commit("feat: add a feature", &["README.md"]).unwrap();
+    repo.tag("v1.3.0").unwrap();
+    repo.tag("v1.3").unwrap();
+    repo.tag("deploy-2023").unwrap();
+
+    let output = nextsv(&repo, &["tags"]);
+
+    assert!(output.status.success());
+    let expected = "  deploy-2023 (no prefix)
+  v1.2.3 parsed as v1.2.3
+  v1.3 unparsed: Version must have three components but only 2 found
+* v1.3.0 parsed as v1.3.0 (latest)
+";
+    assert_eq!(expected, stdout(&output));
+}
+
+#[test]
+fn audit_reports_the_issues_and_fails_when_strict() {
+    let repo = TempRepo::new().unwrap();
+    repo.commit("chore: initial commit", &["README.md"])
+        .unwrap();
+    repo.tag("v1.0.0").unwrap();
+    repo.commit("feat: add a feature", &["README.md"]).unwrap();
+    repo.tag("v1.2.0").unwrap();
+
+    let output = nextsv(&repo, &["audit"]);
+
+    assert!(output.status.success());
+    let expected = "2 version(s) tagged, 1 issue(s) found
+- gap from v1.0.0 to v1.2.0
+";
+    assert_eq!(expected, stdout(&output));
+
+    let output = nextsv(&repo, &["audit", "--strict"]);
+
+    assert_eq!(Some(10), output.status.code());
+    assert_eq!(expected, stdout(&output));
+}
+
+#[test]
+fn docker_tag_sanitizes_build_metadata() {
+    let repo = minor_fixture();
+    let head = repo.repo().head().unwrap().peel_to_commit().unwrap().id();
+
+    let output = nextsv(&repo, &["--build-hash", "--abbrev", "7", "--docker-tag"]);
+
+    assert!(output.status.success());
+    assert_eq!(
+        format!("v1.3.0-{}\n", &head.to_string()[..7]),
+        stdout(&output)
+    );
+}
+
+#[test]
+fn output_file_receives_the_output() {
+    let repo = minor_fixture();
+
+    let output = nextsv(&repo, &["--output-file", "out/next.txt"]);
+    assert!(output.status.success());
+    assert_eq!("", stdout(&output));
+    let content = std::fs::read_to_string(repo.path().join("out/next.txt")).unwrap();
+    assert_eq!("v1.3.0\n", content);
+
+    let output = nextsv(
+        &repo,
+        &[
+            "--number",
+            "--level",
+            "--no-newline",
+            "--output-file",
+            "out/next.txt",
+        ],
+    );
+    assert!(output.status.success());
+    let content = std::fs::read_to_string(repo.path().join("out/next.txt")).unwrap();
+    assert_eq!("version: v1.3.0\nlevel: minor", content);
+}
+
+#[test]
+fn environment_variables_set_for_level_and_version() {
+    let repo = minor_fixture();
+
+    let output = nextsv(&repo, &["-vv"]);
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("setting NEXTSV_LEVEL=\"minor\""),
+        "{}",
+        stderr
+    );
+    assert!(
+        stderr.contains("setting NEXTSV_VERSION=\"v1.3.0\""),
+        "{}",
+        stderr
+    );
+
+    let output = nextsv(&repo, &["-vv", "--set-version-env", "NEXT_VERSION"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("setting NEXT_VERSION=\"v1.3.0\""),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn tag_with_yes_creates_the_tag() {
+    let repo = minor_fixture();
+
+    let output = nextsv(&repo, &["--tag", "--yes"]);
+
+    assert!(output.status.success());
+    assert_eq!("v1.3.0\n", stdout(&output));
+    let head = repo.repo().head().unwrap().peel_to_commit().unwrap().id();
+    let tag = repo.repo().revparse_single("refs/tags/v1.3.0").unwrap();
+    assert_eq!(head, tag.peel_to_commit().unwrap().id());
+}
+
+#[test]
+fn tag_failure_reports_and_writes_nothing() {
+    let repo = minor_fixture();
+    // the ref refs/tags/v1.3.0/rejected stops refs/tags/v1.3.0 being created
+    repo.tag("v1.3.0/rejected").unwrap();
+
+    let output = nextsv(
+        &repo,
+        &[
+            "--tag",
+            "--yes",
+            "--env-file",
+            "release.env",
+            "--output-file",
+            "next.txt",
+        ],
+    );
+
+    assert_eq!(Some(12), output.status.code());
+    assert_eq!("", stdout(&output));
+    assert!(repo.repo().revparse_single("refs/tags/v1.3.0").is_err());
+    assert!(!repo.path().join("release.env").exists());
+    assert!(!repo.path().join("next.txt").exists());
+}
+
+#[test]
+fn tag_without_yes_and_no_terminal_is_not_created() {
+    let repo = minor_fixture();
+
+    let output = nextsv(&repo, &["--tag"]);
+
+    assert_eq!(Some(10), output.status.code());
+    assert_eq!("", stdout(&output));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Tag v1.3.0 was not created"), "{}", stderr);
+    assert!(repo.repo().revparse_single("refs/tags/v1.3.0").is_err());
+}
+
+#[test]
+fn describe_reports_head_when_there_is_no_change() {
+    let repo = TempRepo::new().unwrap();
+    repo.commit("chore: initial commit", &["README.md"])
+        .unwrap();
+    repo.tag("v1.2.3").unwrap();
+    for n in 1..=5 {
+        repo.commit(&format!("update the readme {}", n), &["README.md"])
+            .unwrap();
+    }
+
+    let output = nextsv(&repo, &["--describe"]);
+
+    assert!(output.status.success());
+    let description = stdout(&output);
+    let hash = description
+        .trim_end()
+        .strip_prefix("v1.2.3-5-g")
+        .unwrap_or_else(|| panic!("{}", description));
+    assert!(hash.len() >= 7 && hash.chars().all(|c| c.is_ascii_hexdigit()));
+}
+
+#[test]
+fn describe_reports_the_next_version_for_a_change() {
+    let repo = minor_fixture();
+
+    let output = nextsv(&repo, &["--describe"]);
+
+    assert!(output.status.success());
+    assert_eq!("v1.3.0\n", stdout(&output));
+}
+
+#[test]
+fn tag_glob_selects_the_version_tags() {
+    let repo = TempRepo::new().unwrap();
+    repo.commit("chore: initial commit", &["README.md"])
+        .unwrap();
+    repo.tag("release/v1.2.3").unwrap();
+    repo.tag("v2.0.0").unwrap();
+    repo.commit("fix: correct a bug", &["README.md"]).unwrap();
+
+    let output = nextsv(&repo, &["--tag-glob", "release/v*"]);
+
+    assert!(output.status.success());
+    assert_eq!("release/v1.2.4\n", stdout(&output));
+
+    let output = nextsv(&repo, &["--tag-glob", "release/*/v*"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn pre_release_counter_read_from_the_environment() {
+    let repo = minor_fixture();
+    let args = ["--pre-release", "ci", "--pre-release-from-env", "CI_RUN"];
+
+    let output = Command::new(env!("CARGO_BIN_EXE_nextsv"))
+        .current_dir(repo.path())
+        .args(args)
+        .env("CI_RUN", "427")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!("v1.3.0-ci.427\n", stdout(&output));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_nextsv"))
+        .current_dir(repo.path())
+        .args(args)
+        .env_remove("CI_RUN")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert_eq!("", stdout(&output));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Environment variable CI_RUN is not set or is empty"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn post_release_dev_reports_the_development_version() {
+    let repo = TempRepo::new().unwrap();
+    repo.commit("chore: initial commit", &["README.md"])
+        .unwrap();
+    repo.tag("v1.1.0").unwrap();
+    repo.commit("feat: add a feature", &["README.md"]).unwrap();
+
+    let output = nextsv(&repo, &[]);
+    assert_eq!("v1.2.0\n", stdout(&output));
+
+    let output = nextsv(&repo, &["--post-release-dev", "dev"]);
+    assert!(output.status.success());
+    assert_eq!("v1.3.0-dev.0\n", stdout(&output));
+}
+
+#[test]
+fn semver_strict_applies_the_stable_rules_before_1_0_0() {
+    let repo = TempRepo::new().unwrap();
+    repo.commit("chore: initial commit", &["README.md"])
+        .unwrap();
+    repo.tag("v0.3.0").unwrap();
+    repo.commit("feat: add a feature", &["README.md"]).unwrap();
+
+    let cases: [(&[&str], &str); 2] = [(&[], "v0.3.1\n"), (&["--semver-strict"], "v0.4.0\n")];
+    for (args, expected) in cases {
+        let output = nextsv(&repo, args);
+        assert_eq!(expected, stdout(&output), "{:?}", args);
+    }
+
+    repo.commit("feat!: replace the interface", &["README.md"])
+        .unwrap();
+    let cases: [(&[&str], &str); 3] = [
+        (&[], "v0.4.0\n"),
+        (&["--semver-strict"], "v1.0.0\n"),
+        (&["--semver-strict", "--zero-feat-as-patch"], "v1.0.0\n"),
+    ];
+    for (args, expected) in cases {
+        let output = nextsv(&repo, args);
+        assert_eq!(expected, stdout(&output), "{:?}", args);
+    }
+}
+
+#[test]
+fn changelog_as_json_groups_the_commits() {
+    let repo = TempRepo::new().unwrap();
+    repo.commit("chore: initial commit", &["README.md"])
+        .unwrap();
+    repo.tag("v1.2.3").unwrap();
+    repo.commit("fix: correct a bug\n\nCloses #12", &["README.md"])
+        .unwrap();
+    repo.commit("feat(cli): add a flag", &["README.md"])
+        .unwrap();
+    repo.commit("feat!: change the output", &["README.md"])
+        .unwrap();
+
+    let output = nextsv(&repo, &["--changelog", "--changelog-format", "json"]);
+
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!("v2.0.0", json["version"]);
+    let date = json["date"].as_str().unwrap();
+    assert!(
+        date.len() == 10 && date.chars().filter(|c| *c == '-').count() == 2,
+        "{}",
+        date
+    );
+    let titles: Vec<&str> = json["sections"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|section| section["title"].as_str().unwrap())
+        .collect();
+    assert!(
+        titles.contains(&"Features") && titles.contains(&"Bug Fixes"),
+        "{:?}",
+        titles
+    );
+    for section in json["sections"].as_array().unwrap() {
+        for entry in section["entries"].as_array().unwrap() {
+            for key in ["scope", "description", "breaking", "refs"] {
+                assert!(entry.get(key).is_some(), "{} missing from {}", key, entry);
+            }
+        }
+    }
+    let fixes = json["sections"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|section| section["title"] == "Bug Fixes")
+        .unwrap();
+    assert_eq!(serde_json::json!(["#12"]), fixes["entries"][0]["refs"]);
+}
+
+#[test]
+fn changelog_defaults_to_markdown() {
+    let repo = minor_fixture();
+
+    let output = nextsv(&repo, &["--changelog"]);
+
+    assert!(output.status.success());
+    let changelog = stdout(&output);
+    assert!(changelog.starts_with("## [v1.3.0] - "), "{}", changelog);
+    assert!(changelog.contains("- add a feature\n"), "{}", changelog);
+}
+
+#[test]
+fn compare_url_links_the_changelog_heading() {
+    let repo = minor_fixture();
+
+    let output = nextsv(
+        &repo,
+        &[
+            "--changelog",
+            "--compare-url",
+            "https://github.com/owner/repo/",
+        ],
+    );
+
+    assert!(output.status.success());
+    let changelog = stdout(&output);
+    assert!(
+        changelog.ends_with(
+            "- add a feature\n\n[v1.3.0]: https://github.com/owner/repo/compare/v1.2.3...v1.3.0\n"
+        ),
+        "{}",
+        changelog
+    );
+}
+
+#[test]
+fn changelog_sort_orders_the_entries() {
+    let repo = TempRepo::new().unwrap();
+    repo.commit("chore: initial commit", &["README.md"])
+        .unwrap();
+    repo.tag("v1.2.3").unwrap();
+    for message in [
+        "feat(cli): add a flag",
+        "feat: add logging",
+        "feat(api): add a method",
+    ] {
+        repo.commit(message, &["README.md"]).unwrap();
+    }
+    let cases = [
+        ("newest", ["add a method", "add logging", "add a flag"]),
+        ("date", ["add a flag", "add logging", "add a method"]),
+        ("scope", ["add a method", "add a flag", "add logging"]),
+    ];
+
+    for (sort, expected) in cases {
+        let output = nextsv(
+            &repo,
+            &[
+                "--changelog",
+                "--changelog-format",
+                "json",
+                "--changelog-sort",
+                sort,
+            ],
+        );
+
+        assert!(output.status.success(), "{}", sort);
+        let json: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+        let descriptions: Vec<&str> = json["sections"][0]["entries"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|entry| entry["description"].as_str().unwrap())
+            .collect();
+        assert_eq!(expected.to_vec(), descriptions, "{}", sort);
+    }
+}
+
+#[test]
+fn reset_prerelease_restarts_the_series() {
+    let repo = TempRepo::new().unwrap();
+    repo.commit("chore: initial commit", &["README.md"])
+        .unwrap();
+    repo.tag("v1.1.0").unwrap();
+    repo.commit("feat: add a feature", &["README.md"]).unwrap();
+    repo.tag("v1.2.0-rc.5").unwrap();
+    repo.commit("fix: correct a bug", &["README.md"]).unwrap();
+
+    let output = nextsv(&repo, &["--pre-release", "rc"]);
+    assert_eq!("v1.2.0-rc.6\n", stdout(&output));
+
+    let output = nextsv(&repo, &["--pre-release", "rc", "--reset-prerelease"]);
+
+    assert!(output.status.success());
+    assert_eq!("v1.2.0-rc.1\n", stdout(&output));
+}
+
+#[test]
+fn previews_lists_each_level() {
+    let repo = minor_fixture();
+
+    let output = nextsv(&repo, &["--previews"]);
+
+    assert!(output.status.success());
+    assert_eq!(
+        "patch: v1.2.4\nminor: v1.3.0\nmajor: v2.0.0\n",
+        stdout(&output)
+    );
+}
+
+#[test]
+fn output_jsonl_reports_the_package_on_one_line() {
+    let repo = minor_fixture();
+
+    let output = nextsv(&repo, &["--output", "jsonl"]);
+
+    assert!(output.status.success());
+    let stdout = stdout(&output);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(1, lines.len(), "{}", stdout);
+    let line: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+    let name = repo.path().file_name().unwrap().to_string_lossy();
+    assert_eq!(
+        serde_json::json!({
+            "name": name,
+            "previous": "v1.2.3",
+            "next": "v1.3.0",
+            "level": "minor",
+        }),
+        line
+    );
+    assert!(lines[0].starts_with(&format!("{{\"name\":\"{}\",\"previous\"", name)));
+}
+
+#[test]
+fn strip_prefix_applies_to_every_output() {
+    let repo = minor_fixture();
+    let cases: [(&[&str], &str); 6] = [
+        (&[], "1.3.0\n"),
+        (&["--number", "--level"], "version: 1.3.0\nlevel: minor\n"),
+        (&["--describe"], "1.3.0\n"),
+        (
+            &["--previews"],
+            "patch: 1.2.4\nminor: 1.3.0\nmajor: 2.0.0\n",
+        ),
+        (&["--post-release-dev", "dev"], "1.4.0-dev.0\n"),
+        (&["--changelog", "--changelog-format", "json"], "\"1.3.0\""),
+    ];
+
+    for (flags, expected) in cases {
+        let mut args = vec!["--strip-prefix"];
+        args.extend_from_slice(flags);
+
+        let output = nextsv(&repo, &args);
+
+        assert!(output.status.success(), "{:?}", flags);
+        let reported = stdout(&output);
+        assert!(reported.contains(expected), "{:?}: {}", flags, reported);
+        assert!(!reported.contains("v1."), "{:?}: {}", flags, reported);
+    }
+}
+
+#[test]
+fn display_prefix_is_independent_of_the_tag_prefix() {
+    let repo = TempRepo::new().unwrap();
+    repo.commit("chore: initial commit", &["README.md"])
+        .unwrap();
+    repo.tag("1.2.3").unwrap();
+    repo.commit("feat: add a feature", &["README.md"]).unwrap();
+
+    let output = nextsv(
+        &repo,
+        &["--prefix", "", "--display-prefix", "v", "--tag", "--yes"],
+    );
+
+    assert!(output.status.success());
+    assert_eq!("v1.3.0\n", stdout(&output));
+    assert!(repo.repo().revparse_single("refs/tags/1.3.0").is_ok());
+    assert!(repo.repo().revparse_single("refs/tags/v1.3.0").is_err());
+
+    let output = nextsv(
+        &repo,
+        &["--prefix", "", "--display-prefix", "release-", "--describe"],
+    );
+
+    assert!(output.status.success());
+    assert!(stdout(&output).starts_with("release-1.3.0-0-g"));
+}
+
+#[test]
+fn display_prefix_replaces_the_tag_prefix() {
+    let repo = minor_fixture();
+
+    let output = nextsv(&repo, &["--display-prefix", "version-", "--previews"]);
+
+    assert!(output.status.success());
+    assert_eq!(
+        "patch: version-1.2.4\nminor: version-1.3.0\nmajor: version-2.0.0\n",
+        stdout(&output)
+    );
+}
+
+#[test]
+fn tag_template_names_the_tag_created() {
+    let repo = TempRepo::new().unwrap();
+    repo.commit("chore: initial commit", &["README.md"])
+        .unwrap();
+    repo.tag("v1.2.3-stable").unwrap();
+    repo.commit("feat: add a feature", &["README.md"]).unwrap();
+
+    let output = nextsv(
+        &repo,
+        &["--tag-template", "{prefix}{core}-stable", "--tag", "--yes"],
+    );
+
+    assert!(output.status.success());
+    assert_eq!("v1.3.0\n", stdout(&output));
+    assert!(repo
+        .repo()
+        .revparse_single("refs/tags/v1.3.0-stable")
+        .is_ok());
+}
+
+#[test]
+fn tag_template_names_the_tag_to_confirm() {
+    let repo = TempRepo::new().unwrap();
+    repo.commit("chore: initial commit", &["README.md"])
+        .unwrap();
+    repo.tag("release/v1.2.3").unwrap();
+    repo.commit("feat: add a feature", &["README.md"]).unwrap();
+
+    let output = nextsv(
+        &repo,
+        &["--tag-template", "release/{prefix}{version}", "--tag"],
+    );
+
+    assert_eq!(Some(10), output.status.code());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Tag release/v1.3.0 was not created"),
+        "{}",
+        stderr
+    );
+    assert!(repo
+        .repo()
+        .revparse_single("refs/tags/release/v1.3.0")
+        .is_err());
+}